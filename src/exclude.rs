@@ -5,7 +5,9 @@ use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use syn::{Attribute, Field, Generics, Ident, Visibility};
 
-use crate::utils::{filter_forward_attrs, ForwardAttrsFilter, IdentList};
+use crate::utils::{
+    check_generated_idents, filter_forward_attrs, used_generics, ForwardAttrsFilter, IdentList,
+};
 
 #[derive(Debug, FromMeta)]
 struct ExcludeArgs {
@@ -17,6 +19,10 @@ struct ExcludeArgs {
 
     #[darling(default)]
     forward_attrs: ForwardAttrsFilter,
+
+    /// Ignore listed variants that do not exist instead of reporting them
+    #[darling(default)]
+    lenient: bool,
 }
 
 #[derive(Debug, FromVariant)]
@@ -69,8 +75,27 @@ pub fn exclude(input: TokenStream) -> TokenStream {
     let vis = input.vis;
     let ident = input.ident;
     let generics = input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let variants = input.data.take_enum().unwrap();
 
+    let all_variants = variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect::<Vec<_>>();
+    let mut errors = darling::Error::accumulator();
+    errors.handle(check_generated_idents(
+        "enum",
+        &ident,
+        input.args.iter().map(|arg| &arg.ident),
+        [],
+    ));
+    for arg in input.args.iter().filter(|arg| !arg.lenient) {
+        errors.handle(arg.variants.check("variant", &all_variants));
+    }
+    if let Err(err) = errors.finish() {
+        return TokenStream::from(err.write_errors());
+    }
+
     let excludes = input.args.iter().map(|arg| {
         let derive_attr = arg.derive.as_ref().map(|derives| {
             let derives = derives.iter();
@@ -88,6 +113,7 @@ pub fn exclude(input: TokenStream) -> TokenStream {
 
         let mut variant_idents = Vec::new();
         let mut variant_declares = Vec::new();
+        let mut field_types = Vec::new();
         let mut variant_from_exclude = Vec::new();
 
         variants.iter().for_each(|variant| {
@@ -107,6 +133,7 @@ pub fn exclude(input: TokenStream) -> TokenStream {
             let discriminant = &variant.discriminant;
 
             variant_idents.push(variant_ident);
+            field_types.extend(fields.iter().map(|field| &field.ty));
             variant_declares.push(quote! {
                 #(#forward_attrs)*
                 #variant_ident #fields #discriminant
@@ -136,17 +163,18 @@ pub fn exclude(input: TokenStream) -> TokenStream {
             });
         });
 
-        // TODO: Generics may not be needed in the generated struct
-        // It may be better to check all fields for generics
+        let exclude_generics = used_generics(&generics, field_types);
+        let (_, exclude_ty_generics, exclude_where_clause) = exclude_generics.split_for_impl();
+
         quote! {
             #derive_attr
             #(#forward_attrs)*
-            #vis enum #exclude_ident #generics {
+            #vis enum #exclude_ident #exclude_generics #exclude_where_clause {
                 #(#variant_declares),*
             }
 
-            impl #generics core::convert::From<#exclude_ident #generics> for #ident #generics {
-                fn from(src: #exclude_ident #generics) -> Self {
+            impl #impl_generics core::convert::From<#exclude_ident #exclude_ty_generics> for #ident #ty_generics #where_clause {
+                fn from(src: #exclude_ident #exclude_ty_generics) -> Self {
                     match src {
                         #(#variant_from_exclude),*
                     }
@@ -51,6 +51,31 @@ mod utils;
 /// - `From<Article>` for `PartialArticle`
 /// - `From<PartialArticle>` for `Article`
 ///
+/// And `PartialArticle::apply_to(self, target: &mut Article)` overwrites only the fields that are
+/// `Some`, which is handy for patch-like updates. `PartialArticle::merge(self, higher)` merges two
+/// partials where the fields set in `higher` win, and `PartialArticle::resolve(self, base: Article)`
+/// applies the partial onto `base`.
+///
+/// To inspect which fields are set, `PartialArticle::FIELD_NAMES`, `is_empty()`, `len()` and
+/// `set_field_names()` are generated as well.
+///
+/// With `try_from`, `TryFrom<PartialArticle>` for `Article` is generated instead of `From`. It
+/// fails with `PartialArticleError` listing every field that is `None` and has no `default`.
+///
+/// With `diff`, `PartialArticle::diff(old: &Article, new: &Article)` constructs a partial holding
+/// only the fields of `new` which differ from `old`.
+///
+/// With `builder`, `Article::builder()` returns an `ArticleBuilder` holding the fields as an empty
+/// `PartialArticle` does, with a setter per field and a `build()` method. Kept fields start unset
+/// too, and with `try_from` the ones without a `default` are reported as missing by `build()`,
+/// while without it they must have a `default`.
+/// With `builder(typestate)`, the `build()` of `ArticleBuilder` is only available once every field
+/// without a `default` is set.
+///
+/// Each `arg(...)` generates another struct with its own options. Only `forward_attrs` is
+/// inherited from the container level, the other container level options apply to the struct named
+/// by the container level `ident`, and giving them without `ident` is an error.
+///
 /// ## Attributes
 ///
 /// ```ignore
@@ -59,12 +84,45 @@ mod utils;
 ///     ident = <IDENT>, // The identifier of the generated struct
 ///     [derive(<DERIVE>, ...)], // Derive attributes for the generated struct
 ///     [forward_attrs(<ATTR, ...> | not(<ATTR, ...>))], // Forward specific attributes to the generated struct
+///     [fields(<FIELD>, ...)], // Only make the given fields optional, the others keep their types
+///     [flatten_option], // Keep `Option<T>` fields as `Option<T>` instead of `Option<Option<T>>`
+///     [try_from], // Generate `TryFrom<IDENT>` and `IDENTError` instead of `From<IDENT>`
+///     [diff], // Generate `IDENT::diff(old: &BasedStruct, new: &BasedStruct)`
+///         // Requires every field to implement `Clone` and `PartialEq`
+///         // Cannot be used with flattened `Option<T>` fields, use `merge_patch` instead
+///     [merge_patch[(
+///         [ident = <PATCH_IDENT>], // The identifier of the generated tri-state enum, `<IDENT>Patch` by default
+///         [serde], // Generate serde glue, the generated struct should derive `Serialize` and `Deserialize`
+///     )]], // Represent `Option<T>` fields with `PATCH_IDENT<T>` following JSON Merge Patch (RFC 7396)
+///     [builder[(
+///         [typestate], // Generate a typestate builder, `build` is only available once all required fields are set
+///         [ident = <BUILDER_IDENT>], // The identifier of the builder, `<BasedStruct>Builder` by default
+///     )]], // Generate `BasedStruct::builder()` with a setter per field and `build()`, for one struct only
+///     [merge_trait = <TRAIT>], // Implement a user defined trait with `fn merge(self, higher: Self) -> Self`
+///     [arg(
+///         ident = <IDENT>, // The identifier of another generated struct
+///         ..., // Any of the options above, `forward_attrs` overrides the container level one
+///     ), ...], // Generate more structs from the same struct, `ident` above becomes optional
+///         // Without `ident`, the container level only accepts `forward_attrs`
 /// )]
 /// pub struct BasedStruct {
 ///     #[partial(
 ///         [default = <DEFAULT>], // The default value of the field in the generated From impl
+///             // For a flattened `Option<T>` field, this is an `Option<T>` used when the field is `None`
 ///         [forward_attrs(<ATTR, ...> | not(<ATTR, ...>))], // Forward specific attributes to the generated field
 ///             // If given, will override the container level `forward_attrs`
+///         [flatten_option [= <BOOL>]], // If given, will override the container level `flatten_option`
+///         [nested = <PARTIAL>], // Use the partial type of the field, which also derives `Partial`
+///             // The generated field becomes `Option<PARTIAL>` and conversions recurse into it
+///             // With `try_from`, a partial which fails to convert is reported as missing as a whole
+///         [skip [= <BOOL>]], // Keep the field out of the generated struct
+///             // The generated From impl fills it with `default` or `Default::default()`
+///         [keep [= <BOOL>]], // Keep the type of the field instead of making it optional
+///             // If given, will override the container level `fields`
+///         [arg(
+///             ident = <IDENT>, // The generated struct these options apply to
+///             ..., // Any of the options above, overriding the ones given directly
+///         ), ...],
 ///     )]
 ///     field: FieldType,
 /// }
@@ -98,6 +156,20 @@ pub fn partial(input: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// `From<RequiredArticle>` for `Article` is also generated, wrapping the unwrapped fields back in
+/// `Some`.
+///
+/// With `try_from`, `TryFrom<Article>` for `RequiredArticle` is generated as well. It fails with
+/// `RequiredArticleError` listing every field that is `None` and has no `default`.
+///
+/// If the struct also derives `Partial`, `try_from_partial = PartialArticle` generates
+/// `TryFrom<PartialArticle>` for `RequiredArticle`, which also reports the fields not set in the
+/// partial.
+///
+/// Each `arg(...)` generates another struct with its own options. Only `forward_attrs` is
+/// inherited from the container level, the other container level options apply to the struct named
+/// by the container level `ident`, and giving them without `ident` is an error.
+///
 /// ## Attributes
 ///
 /// ```ignore
@@ -106,11 +178,36 @@ pub fn partial(input: TokenStream) -> TokenStream {
 ///     ident = <IDENT>, // The identifier of the generated struct
 ///     [derive(<DERIVE>, ...)], // Derive attributes for the generated struct
 ///     [forward_attrs(<ATTR, ...> | not(<ATTR, ...>))], // Forward specific attributes to the generated struct
+///     [fields(<FIELD>, ...)], // Only unwrap the given fields, the others keep their types
+///     [try_from], // Generate `TryFrom<BasedStruct>` for `IDENT` and `IDENTError`
+///     [unwrap(<WRAPPER> [= <UNWRAP>], ...)], // Unwrap these types like `Option`, e.g. type aliases
+///         // `UNWRAP` converts the wrapper into `Option<T>`, `Into<Option<T>>` is used by default
+///         // The wrapper should implement `From<T>` for the generated From impl
+///     [deep], // Strip every optional layer, e.g. `Option<Option<T>>` becomes `T`
+///     [try_from_partial = <PARTIAL>], // Generate `TryFrom<PARTIAL>` for `IDENT` and `IDENTError`
+///         // `PARTIAL` should be derived by `Partial` on the same struct
+///     [arg(
+///         ident = <IDENT>, // The identifier of another generated struct
+///         ..., // Any of the options above, `forward_attrs` overrides the container level one
+///     ), ...], // Generate more structs from the same struct, `ident` above becomes optional
+///         // Without `ident`, the container level only accepts `forward_attrs`
 /// )]
 /// pub struct BasedStruct {
 ///     #[required(
 ///         [forward_attrs(<ATTR, ...> | not(<ATTR, ...>))], // Forward specific attributes to the generated field
 ///             // If given, will override the container level `forward_attrs`
+///         [keep [= <BOOL>]], // Keep `Option<T>` instead of unwrapping it
+///             // If given, will override the container level `fields`
+///         [default = <DEFAULT>], // The value used in the generated TryFrom impl when the field is `None`
+///         [unwrap(<WRAPPER> [= <UNWRAP>], ...)], // Unwrap these types in addition to the container level ones
+///         [deep [= <BOOL>]], // If given, will override the container level `deep`
+///         [nested = <REQUIRED>], // Use the required type of the field, which also derives `Required`
+///             // The generated conversions recurse into it, a failed one is reported as missing
+///             // as a whole, without the fields missing inside it
+///         [arg(
+///             ident = <IDENT>, // The generated struct these options apply to
+///             ..., // Any of the options above, overriding the ones given directly
+///         ), ...],
 ///     )]
 ///     field: FieldType,
 /// }
@@ -168,9 +265,18 @@ pub fn required(input: TokenStream) -> TokenStream {
 ///     arg(
 ///         ident = <IDENT>, // The identifier of the generated struct
 ///         fields(<FIELD>, ...), // The fields to pick from the original struct
+///             // For tuple structs, fields are given by their index, e.g. `fields(0, 2)`
 ///         [derive(<DERIVE>, ...)], // Derive attributes for the generated struct
 ///         [forward_attrs(<ATTR, ...> | not(<ATTR, ...>))], // Forward specific attributes to the generated struct
 ///             // If given, will override the container level `forward_attrs`
+///         [lenient], // Ignore listed fields that do not exist instead of failing to compile
+///         [split[(ident = <IDENT>)]], // Also generate the struct of the remaining fields, `<IDENT>Rest` by default,
+///             // `split_<ident>(self)` returning both parts and `From<(<IDENT>, <REST>)>` joining them back
+///         [assign], // Also generate `assign_<ident>(&mut self, value: <IDENT>)` overwriting the picked fields
+///         [ref_view[(ident = <IDENT>, derive(<DERIVE>, ...))]], // Also generate the view struct `<IDENT>Ref<'view>` borrowing the fields,
+///             // and `as_<ident>(&self)` returning it
+///         [mut_view[(ident = <IDENT>, derive(<DERIVE>, ...))]], // Also generate the view struct `<IDENT>Mut<'view>` mutably borrowing
+///             // the fields, and `as_<ident>_mut(&mut self)` returning it
 ///     ), ...
 /// )]
 /// pub struct BasedStruct {
@@ -233,9 +339,17 @@ pub fn pick(input: TokenStream) -> TokenStream {
 ///     arg(
 ///         ident = <IDENT>, // The identifier of the generated struct
 ///         fields(<FIELD>, ...), // The fields to omit from the original struct
+///             // For tuple structs, fields are given by their index, e.g. `fields(0, 2)`
 ///         [derive(<DERIVE>, ...)], // Derive attributes for the generated struct
 ///         [forward_attrs(<ATTR, ...> | not(<ATTR, ...>))], // Forward specific attributes to the generated struct
 ///             // If given, will override the container level `forward_attrs`
+///         [lenient], // Ignore listed fields that do not exist instead of failing to compile
+///         [split[(ident = <IDENT>)]], // Also generate the struct of the omitted fields, `<IDENT>Rest` by default,
+///             // `split_<ident>(self)` returning both parts and `From<(<IDENT>, <REST>)>` joining them back
+///         [ref_view[(ident = <IDENT>, derive(<DERIVE>, ...))]], // Also generate the view struct `<IDENT>Ref<'view>` borrowing the fields,
+///             // and `as_<ident>(&self)` returning it
+///         [mut_view[(ident = <IDENT>, derive(<DERIVE>, ...))]], // Also generate the view struct `<IDENT>Mut<'view>` mutably borrowing
+///             // the fields, and `as_<ident>_mut(&mut self)` returning it
 ///     ), ...
 /// )]
 /// pub struct BasedStruct {
@@ -287,6 +401,7 @@ pub fn omit(input: TokenStream) -> TokenStream {
 ///         [derive(<DERIVE>, ...)], // Derive attributes for the generated enum
 ///         [forward_attrs(<ATTR, ...> | not(<ATTR, ...>))], // Forward specific attributes to the generated enum
 ///             // If given, will override the container level `forward_attrs`
+///         [lenient], // Ignore listed variants that do not exist instead of failing to compile
 ///     ), ...
 /// )]
 /// pub enum BasedEnum {
@@ -340,6 +455,7 @@ pub fn extract(input: TokenStream) -> TokenStream {
 ///         [derive(<DERIVE>, ...)], // Derive attributes for the generated enum
 ///         [forward_attrs(<ATTR, ...> | not(<ATTR, ...>))], // Forward specific attributes to the generated enum
 ///             // If given, will override the container level `forward_attrs`
+///         [lenient], // Ignore listed variants that do not exist instead of failing to compile
 ///     ),
 /// )]
 /// pub enum BasedEnum {
@@ -1,22 +1,52 @@
 use darling::ast::Data;
-use darling::util::{Ignored, PathList};
+use darling::util::{Ignored, Override, PathList};
 use darling::{FromDeriveInput, FromField, FromMeta};
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::{Attribute, Generics, Ident, Type, Visibility};
 
-use crate::utils::{filter_forward_attrs, ForwardAttrsFilter, IdentList};
+use crate::utils::{
+    check_generated_idents, field_member, filter_forward_attrs, rest_ident, split_impls,
+    struct_body, used_generics, FieldsPart, ForwardAttrsFilter, MemberList, SplitArgs, View,
+    ViewArgs,
+};
 
 #[derive(Debug, FromMeta)]
 struct OmitArgs {
     ident: Ident,
 
-    fields: IdentList,
+    fields: MemberList,
 
     derive: Option<PathList>,
 
     #[darling(default)]
     forward_attrs: ForwardAttrsFilter,
+
+    /// Ignore listed fields that do not exist instead of reporting them
+    #[darling(default)]
+    lenient: bool,
+
+    /// Also generate the struct of the omitted fields, `<IDENT>Rest` unless named by
+    /// `split(ident = ...)`
+    split: Option<Override<SplitArgs>>,
+
+    /// Also generate the view struct borrowing the fields, `<IDENT>Ref` unless named by
+    /// `ref_view(ident = ...)`
+    ref_view: Option<Override<ViewArgs>>,
+
+    /// Also generate the view struct mutably borrowing the fields, `<IDENT>Mut` unless named by
+    /// `mut_view(ident = ...)`
+    mut_view: Option<Override<ViewArgs>>,
+}
+
+impl OmitArgs {
+    /// Returns the views given by `ref_view` and `mut_view`.
+    fn views(&self) -> Vec<View<'_>> {
+        View::new(&self.ident, self.ref_view.as_ref(), false)
+            .into_iter()
+            .chain(View::new(&self.ident, self.mut_view.as_ref(), true))
+            .collect()
+    }
 }
 
 #[derive(Debug, FromField)]
@@ -35,7 +65,7 @@ struct OmitField {
 }
 
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(omit), forward_attrs, supports(struct_named))]
+#[darling(attributes(omit), forward_attrs, supports(struct_named, struct_tuple))]
 struct OmitInput {
     ident: Ident,
 
@@ -69,8 +99,46 @@ pub fn omit(input: TokenStream) -> TokenStream {
     let vis = input.vis;
     let ident = input.ident;
     let generics = input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let fields = input.data.take_struct().unwrap();
 
+    let members = fields
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| field_member(field.ident.as_ref(), index))
+        .collect::<Vec<_>>();
+    let mut errors = darling::Error::accumulator();
+    // The other structs generated along with the ones given by `arg` by their options
+    let derived_idents = input
+        .args
+        .iter()
+        .flat_map(|arg| {
+            let views = arg.views().into_iter().map(|view| {
+                let option = if view.mutable { "mut_view" } else { "ref_view" };
+                (option, &arg.ident, view.ident)
+            });
+            rest_ident(&arg.ident, arg.split.as_ref())
+                .map(|rest_ident| ("split", &arg.ident, rest_ident))
+                .into_iter()
+                .chain(views)
+        })
+        .collect::<Vec<_>>();
+    errors.handle(check_generated_idents(
+        "struct",
+        &ident,
+        input.args.iter().map(|arg| &arg.ident),
+        derived_idents
+            .iter()
+            .map(|(option, arg, ident)| (*option, *arg, ident)),
+    ));
+    for arg in input.args.iter().filter(|arg| !arg.lenient) {
+        errors.handle(arg.fields.check(&members));
+    }
+    if let Err(err) = errors.finish() {
+        return TokenStream::from(err.write_errors());
+    }
+
     let omits = input.args.iter().map(|arg| {
         let derive_attr = arg.derive.as_ref().map(|derives| {
             let derives = derives.iter();
@@ -82,20 +150,26 @@ pub fn omit(input: TokenStream) -> TokenStream {
         let forward_attrs = filter_forward_attrs(
             input.attrs.iter(),
             &arg.forward_attrs + &input.forward_attrs,
-        );
+        )
+        .collect::<Vec<_>>();
 
         let omit_ident = &arg.ident;
+        let rest_ident = rest_ident(omit_ident, arg.split.as_ref());
 
-        let mut field_idents = Vec::new();
-        let mut field_declares = Vec::new();
+        let mut kept = FieldsPart::default();
+        let mut rest = FieldsPart::default();
 
-        fields.fields.iter().for_each(|field| {
-            let ident = field.ident.as_ref().unwrap();
+        fields.fields.iter().enumerate().for_each(|(index, field)| {
+            let member = field_member(field.ident.as_ref(), index);
 
             // Check if ident is in the list of fields to Omit
-            if arg.fields.contains(ident) {
+            let part = if !arg.fields.contains(&member) {
+                &mut kept
+            } else if rest_ident.is_some() {
+                &mut rest
+            } else {
                 return;
-            }
+            };
 
             let forward_attrs = filter_forward_attrs(
                 field.attrs.iter(),
@@ -103,31 +177,74 @@ pub fn omit(input: TokenStream) -> TokenStream {
             );
 
             let vis = &field.vis;
+            let name = field.ident.as_ref().map(|ident| quote! { #ident: });
             let ty = &field.ty;
 
-            field_idents.push(ident);
-            field_declares.push(quote! {
-                #(#forward_attrs)*
-                #vis #ident: #ty,
-            });
+            part.push(
+                field.ident.as_ref(),
+                member,
+                quote! {
+                    #(#forward_attrs)*
+                    #vis #name
+                },
+                ty,
+            );
         });
 
-        // TODO: Generics may not be needed in the generated struct
-        // It may be better to check all fields for generics
-        quote! {
-            #derive_attr
-            #(#forward_attrs)*
-            #vis struct #omit_ident #generics {
-                #(#field_declares)*
-            }
+        let generate = |generated_ident: &Ident, part: &FieldsPart| {
+            let generated_generics = used_generics(&generics, part.types.iter().copied());
+            let (_, generated_ty_generics, generated_where_clause) =
+                generated_generics.split_for_impl();
+            let field_declares = part.declares(|ty| quote! { #ty });
+            let struct_body = struct_body(fields.style, generated_where_clause, &field_declares);
+            let field_inits = part.inits();
 
-            impl #generics From<#ident #generics> for #omit_ident #generics {
-                fn from(src: #ident #generics) -> Self {
-                    Self {
-                        #(#field_idents: src.#field_idents),*
+            let tokens = quote! {
+                #derive_attr
+                #(#forward_attrs)*
+                #vis struct #generated_ident #generated_generics #struct_body
+
+                impl #impl_generics From<#ident #ty_generics> for #generated_ident #generated_ty_generics #where_clause {
+                    fn from(src: #ident #ty_generics) -> Self {
+                        Self {
+                            #(#field_inits),*
+                        }
                     }
                 }
+            };
+            (tokens, generated_generics)
+        };
+
+        let (omit, omit_generics) = generate(omit_ident, &kept);
+        let split = rest_ident.as_ref().map(|rest_ident| {
+            let (rest_struct, rest_generics) = generate(rest_ident, &rest);
+            let split_impls = split_impls(
+                &vis,
+                &ident,
+                &generics,
+                (omit_ident, &omit_generics, &kept),
+                (rest_ident, &rest_generics, &rest),
+            );
+
+            quote! {
+                #rest_struct
+                #split_impls
             }
+        });
+
+        let views = arg.views().into_iter().map(|view| {
+            view.expand(
+                (&vis, &ident, &generics),
+                (omit_ident, &omit_generics, &kept),
+                fields.style,
+                &forward_attrs,
+            )
+        });
+
+        quote! {
+            #omit
+            #split
+            #(#views)*
         }
     });
 
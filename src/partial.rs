@@ -1,20 +1,154 @@
-use darling::ast::Data;
-use darling::util::{Ignored, PathList};
+use darling::ast::{Data, Style};
+use darling::util::{Ignored, Override, PathList};
 use darling::{FromDeriveInput, FromField, FromMeta};
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{Attribute, Generics, Ident, Type, Visibility};
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::{parse_quote, Attribute, GenericParam, Generics, Ident, Member, Type, Visibility};
 
-use crate::utils::{filter_forward_attrs, ForwardAttrsFilter};
+use crate::utils::{
+    check_container_options, check_generated_idents, check_names, dropped_generics, field_member,
+    filter_forward_attrs, member_name, missing_fields_error, option_inner_type, struct_body,
+    try_from_body, used_generics, ForwardAttrsFilter, MemberList,
+};
 
 #[derive(Debug, FromMeta)]
 struct PartialArgs {
-    ident: Ident,
+    /// Optional at the container level, where the generated structs may be given by `arg(...)`
+    ident: Option<Ident>,
 
     derive: Option<PathList>,
 
     #[darling(default)]
     forward_attrs: ForwardAttrsFilter,
+
+    /// Only the given fields become optional, the others keep their types
+    fields: Option<MemberList>,
+
+    /// Keep fields that are already `Option<T>` as `Option<T>` instead of `Option<Option<T>>`
+    #[darling(default)]
+    flatten_option: bool,
+
+    /// Generate `TryFrom<Partial>` reporting missing fields instead of `From<Partial>`
+    #[darling(default)]
+    try_from: bool,
+
+    /// Generate `diff` to compute the partial between two values of the original struct
+    #[darling(default)]
+    diff: bool,
+
+    /// Represent `Option<T>` fields with a tri-state type following JSON Merge Patch
+    merge_patch: Option<Override<MergePatchArgs>>,
+
+    /// Generate a builder for the original struct
+    builder: Option<Override<BuilderArgs>>,
+
+    /// A user defined trait with `fn merge(self, higher: Self) -> Self` to implement
+    merge_trait: Option<syn::Path>,
+}
+
+impl PartialArgs {
+    /// Returns the names of the options given besides `ident` and `forward_attrs`.
+    fn given_options(&self) -> Vec<&'static str> {
+        [
+            ("derive", self.derive.is_some()),
+            ("fields", self.fields.is_some()),
+            ("flatten_option", self.flatten_option),
+            ("try_from", self.try_from),
+            ("diff", self.diff),
+            ("merge_patch", self.merge_patch.is_some()),
+            ("builder", self.builder.is_some()),
+            ("merge_trait", self.merge_trait.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(name, given)| given.then_some(name))
+        .collect()
+    }
+
+    /// Returns the identifier of the tri-state enum if `merge_patch` is given.
+    fn patch_ident(&self, partial_ident: &Ident) -> Option<Ident> {
+        self.merge_patch.as_ref().map(|merge_patch| {
+            merge_patch
+                .as_ref()
+                .explicit()
+                .and_then(|merge_patch| merge_patch.ident.clone())
+                .unwrap_or_else(|| format_ident!("{}Patch", partial_ident))
+        })
+    }
+
+    /// Returns the identifier of the builder of the original struct `ident` if `builder` is given.
+    fn builder_ident(&self, ident: &Ident) -> Option<Ident> {
+        self.builder.as_ref().map(|builder| {
+            builder
+                .as_ref()
+                .explicit()
+                .and_then(|builder| builder.ident.clone())
+                .unwrap_or_else(|| format_ident!("{}Builder", ident))
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct MergePatchArgs {
+    /// The identifier of the generated tri-state enum
+    ident: Option<Ident>,
+
+    /// Generate serde glue so that an absent field and `null` deserialize differently
+    #[darling(default)]
+    serde: bool,
+}
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct BuilderArgs {
+    /// Generate a typestate builder, where `build` is only available once all required fields are set
+    #[darling(default)]
+    typestate: bool,
+
+    /// The identifier of the generated builder
+    ident: Option<Ident>,
+}
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct PartialFieldOptions {
+    default: Option<syn::Expr>,
+
+    #[darling(default)]
+    forward_attrs: ForwardAttrsFilter,
+
+    /// Overrides the container level `flatten_option`
+    flatten_option: Option<bool>,
+
+    /// The partial type of the field, which should also derive `Partial`
+    nested: Option<syn::Path>,
+
+    /// Keep the field out of the partial struct
+    skip: Option<bool>,
+
+    /// Keep the type of the field instead of making it optional
+    keep: Option<bool>,
+}
+
+impl PartialFieldOptions {
+    /// Overrides these options with the ones given for a specific generated struct.
+    fn with(&self, arg: &PartialFieldOptions) -> PartialFieldOptions {
+        PartialFieldOptions {
+            default: arg.default.clone().or_else(|| self.default.clone()),
+            forward_attrs: (&arg.forward_attrs + &self.forward_attrs).clone(),
+            flatten_option: arg.flatten_option.or(self.flatten_option),
+            nested: arg.nested.clone().or_else(|| self.nested.clone()),
+            skip: arg.skip.or(self.skip),
+            keep: arg.keep.or(self.keep),
+        }
+    }
+}
+
+/// Field options which only apply to the generated struct named by `ident`
+#[derive(Debug, FromMeta)]
+struct PartialFieldArgs {
+    ident: Ident,
+
+    #[darling(flatten)]
+    options: PartialFieldOptions,
 }
 
 #[derive(Debug, FromField)]
@@ -28,10 +162,52 @@ struct PartialField {
 
     attrs: Vec<Attribute>,
 
-    default: Option<syn::Expr>,
+    #[darling(flatten)]
+    options: PartialFieldOptions,
 
-    #[darling(default)]
-    forward_attrs: ForwardAttrsFilter,
+    #[darling(multiple, rename = "arg")]
+    args: Vec<PartialFieldArgs>,
+}
+
+impl PartialField {
+    /// Returns the options of the field for the generated struct named `partial_ident`.
+    fn options(&self, partial_ident: &Ident) -> PartialFieldOptions {
+        match self.args.iter().find(|arg| arg.ident == *partial_ident) {
+            Some(arg) => self.options.with(&arg.options),
+            None => self.options.clone(),
+        }
+    }
+}
+
+/// How a field of the original struct is represented in the partial struct.
+#[derive(Clone, Copy)]
+enum FieldKind<'a> {
+    /// `T` becomes `Option<T>`
+    Plain,
+    /// `Option<T>` stays `Option<T>`
+    Flatten,
+    /// `T` becomes `Option<PartialT>`
+    Nested(&'a syn::Path),
+    /// `Option<T>` becomes the tri-state merge patch enum of `T`
+    Patch(&'a Type),
+    /// `T` stays `T`
+    Keep,
+}
+
+/// A field of the original struct as seen by the typestate builder.
+struct BuilderField<'a> {
+    ident: &'a Ident,
+    ty: &'a Type,
+    role: BuilderFieldRole,
+}
+
+enum BuilderFieldRole {
+    /// The field must be set before `build` is available
+    Required,
+    /// The field falls back to the given value if not set
+    Optional(proc_macro2::TokenStream),
+    /// The field has no setter and is filled with the given value
+    Skip(proc_macro2::TokenStream),
 }
 
 #[derive(Debug, FromDeriveInput)]
@@ -53,87 +229,1062 @@ struct PartialInput {
 
     #[darling(flatten)]
     args: PartialArgs,
+
+    /// Args for each additional generated struct
+    #[darling(multiple, rename = "arg")]
+    targets: Vec<PartialArgs>,
+}
+
+/// Determines how a field which is not skipped is represented in the partial struct described by
+/// `args`.
+fn field_kind<'a>(
+    args: &PartialArgs,
+    options: &'a PartialFieldOptions,
+    member: &Member,
+    ty: &'a Type,
+) -> darling::Result<FieldKind<'a>> {
+    // Fields not listed in the container level `fields` keep their types, whatever their other
+    // options, which may have been given for the other generated structs
+    if options.keep.is_none()
+        && args
+            .fields
+            .as_ref()
+            .is_some_and(|selected| !selected.contains(member))
+    {
+        return Ok(FieldKind::Keep);
+    }
+    let keep = options.keep.unwrap_or_default();
+
+    match (&options.nested, option_inner_type(ty)) {
+        (Some(_), _) if keep => {
+            Err(darling::Error::custom("`nested` cannot be used with `keep`").with_span(member))
+        }
+        (None, _) if keep => Ok(FieldKind::Keep),
+        (Some(_), _) if options.flatten_option == Some(true) => Err(darling::Error::custom(
+            "`nested` cannot be used with `flatten_option`",
+        )
+        .with_span(member)),
+        (Some(nested), _) => Ok(FieldKind::Nested(nested)),
+        (None, Some(_)) if options.flatten_option.unwrap_or(args.flatten_option) => {
+            Ok(FieldKind::Flatten)
+        }
+        (None, Some(inner)) if args.merge_patch.is_some() => Ok(FieldKind::Patch(inner)),
+        _ => Ok(FieldKind::Plain),
+    }
+}
+
+/// How a field of the original struct is represented in a generated partial struct, for the other
+/// derives converting from it.
+pub(crate) enum PartialFieldShape {
+    /// Not in the partial struct, the original field is filled with the given value
+    Skip(proc_macro2::TokenStream),
+    /// `T` becomes `Option<T>`
+    Plain,
+    /// `Option<T>` stays `Option<T>`
+    Flatten,
+    /// `T` becomes `Option<PartialT>`
+    Nested(syn::Path),
+    /// `Option<T>` becomes the given tri-state merge patch enum of `T`
+    Patch(Ident),
+    /// `T` stays `T`
+    Keep,
+}
+
+/// Returns the generics of the partial struct named `partial_ident` derived from `input`, and the
+/// shape of each field of the original struct in it, together with how the field is accessed in it.
+pub(crate) fn partial_fields(
+    input: &syn::DeriveInput,
+    partial_ident: &Ident,
+) -> darling::Result<(Generics, Vec<(PartialFieldShape, Member)>)> {
+    let input = PartialInput::from_derive_input(input)?;
+    let args = std::iter::once(&input.args)
+        .chain(input.targets.iter())
+        .find(|args| args.ident.as_ref() == Some(partial_ident))
+        .ok_or_else(|| {
+            darling::Error::custom(format!(
+                "no partial struct named `{partial_ident}` is derived"
+            ))
+            .with_span(partial_ident)
+        })?;
+    let patch_ident = args.patch_ident(partial_ident);
+    let fields = input.data.take_struct().unwrap();
+
+    let mut shapes = Vec::new();
+    let mut partial_types = Vec::new();
+    let mut partial_index = 0;
+    for (index, field) in fields.fields.iter().enumerate() {
+        let options = field.options(partial_ident);
+        let member = field_member(field.ident.as_ref(), index);
+
+        if options.skip.unwrap_or_default() {
+            let value = match &options.default {
+                Some(default) => quote! { #default },
+                None => quote! { core::default::Default::default() },
+            };
+            shapes.push((PartialFieldShape::Skip(value), member));
+            continue;
+        }
+
+        let kind = field_kind(args, &options, &member, &field.ty)?;
+        partial_types.push(partial_type(kind, &field.ty));
+        let shape = match kind {
+            FieldKind::Plain => PartialFieldShape::Plain,
+            FieldKind::Flatten => PartialFieldShape::Flatten,
+            FieldKind::Nested(nested) => PartialFieldShape::Nested(nested.clone()),
+            FieldKind::Patch(_) => PartialFieldShape::Patch(patch_ident.clone().unwrap()),
+            FieldKind::Keep => PartialFieldShape::Keep,
+        };
+        shapes.push((shape, field_member(field.ident.as_ref(), partial_index)));
+        partial_index += 1;
+    }
+
+    Ok((used_generics(&input.generics, &partial_types), shapes))
+}
+
+/// Returns the type of a field in the partial struct, up to the wrappers which do not affect the
+/// generic parameters it uses.
+fn partial_type(kind: FieldKind, ty: &Type) -> Type {
+    match kind {
+        FieldKind::Nested(nested) => parse_quote! { #nested },
+        FieldKind::Patch(inner) => inner.clone(),
+        _ => ty.clone(),
+    }
+}
+
+/// Converts a value of the original field into its representation in the partial struct.
+fn from_original(kind: FieldKind, value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    match kind {
+        FieldKind::Plain => quote! { Some(#value) },
+        FieldKind::Flatten | FieldKind::Keep => quote! { #value },
+        FieldKind::Nested(_) => quote! { Some(core::convert::From::from(#value)) },
+        FieldKind::Patch(_) => quote! { core::convert::From::from(#value) },
+    }
+}
+
+/// Generates a builder whose `build` is only available once all required fields are set.
+///
+/// Each required field is tracked by a type parameter, which is `()` while the field is unset and
+/// `(T,)` once it holds a value of type `T`.
+fn typestate_builder(
+    vis: &Visibility,
+    ident: &Ident,
+    generics: &Generics,
+    builder_ident: &Ident,
+    fields: &[BuilderField],
+) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let params = generics
+        .params
+        .iter()
+        .map(|param| match param {
+            GenericParam::Lifetime(param) => param.lifetime.to_token_stream(),
+            GenericParam::Type(param) => param.ident.to_token_stream(),
+            GenericParam::Const(param) => param.ident.to_token_stream(),
+        })
+        .collect::<Vec<_>>();
+
+    // Named after the position of the field, as names derived from the field names may collide,
+    // e.g. `a_b` and `a__b`
+    let states = fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| matches!(field.role, BuilderFieldRole::Required))
+        .map(|(index, _)| format_ident!("__State{}", index))
+        .collect::<Vec<_>>();
+
+    let mut builder_generics = generics.clone();
+    builder_generics
+        .params
+        .extend(states.iter().map(|state| -> GenericParam {
+            parse_quote! { #state }
+        }));
+    let (builder_impl_generics, builder_ty_generics, _) = builder_generics.split_for_impl();
+
+    let mut field_declares = Vec::new();
+    let mut field_unset = Vec::new();
+    let mut field_build = Vec::new();
+    let mut setters = Vec::new();
+    let mut states_iter = states.iter();
+    let mut required_types = Vec::new();
+
+    let field_idents = fields
+        .iter()
+        .filter(|field| !matches!(field.role, BuilderFieldRole::Skip(_)))
+        .map(|field| field.ident)
+        .collect::<Vec<_>>();
+
+    for field in fields {
+        let field_ident = field.ident;
+        let ty = field.ty;
+        let doc = format!("Sets the `{}` field.", field_ident.unraw());
+
+        match &field.role {
+            BuilderFieldRole::Required => {
+                let state = states_iter.next().unwrap();
+                required_types.push(quote! { (#ty,) });
+                field_declares.push(quote! { #field_ident: #state });
+                field_unset.push(quote! { #field_ident: () });
+                field_build.push(quote! { #field_ident: self.#field_ident.0 });
+
+                let next_states = states.iter().map(|other| {
+                    if other == state {
+                        quote! { (#ty,) }
+                    } else {
+                        quote! { #other }
+                    }
+                });
+                let other_fields = field_idents.iter().filter(|other| **other != field_ident);
+                setters.push(quote! {
+                    #[doc = #doc]
+                    #vis fn #field_ident(self, value: #ty) -> #builder_ident<#(#params,)* #(#next_states),*> {
+                        #builder_ident {
+                            #field_ident: (value,),
+                            #(#other_fields: self.#other_fields,)*
+                            __marker: core::marker::PhantomData,
+                        }
+                    }
+                });
+            }
+            BuilderFieldRole::Optional(default) => {
+                field_declares.push(quote! { #field_ident: Option<#ty> });
+                field_unset.push(quote! { #field_ident: None });
+                field_build.push(quote! {
+                    #field_ident: self.#field_ident.unwrap_or_else(|| #default)
+                });
+                setters.push(quote! {
+                    #[doc = #doc]
+                    #vis fn #field_ident(mut self, value: #ty) -> Self {
+                        self.#field_ident = Some(value);
+                        self
+                    }
+                });
+            }
+            BuilderFieldRole::Skip(value) => {
+                field_build.push(quote! { #field_ident: #value });
+            }
+        }
+    }
+
+    let unset_states = states.iter().map(|_| quote! { () });
+    let doc = format!(
+        "A builder for `{ident}`, where `build` is only available once all required fields are set."
+    );
+
+    quote! {
+        #[doc = #doc]
+        #vis struct #builder_ident #builder_impl_generics #where_clause {
+            #(#field_declares,)*
+            __marker: core::marker::PhantomData<fn() -> #ident #ty_generics>,
+        }
+
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Constructs a builder with no field set.
+            #vis fn builder() -> #builder_ident<#(#params,)* #(#unset_states),*> {
+                #builder_ident {
+                    #(#field_unset,)*
+                    __marker: core::marker::PhantomData,
+                }
+            }
+        }
+
+        impl #builder_impl_generics #builder_ident #builder_ty_generics #where_clause {
+            #(#setters)*
+        }
+
+        impl #impl_generics #builder_ident<#(#params,)* #(#required_types),*> #where_clause {
+            /// Builds the value once all required fields are set.
+            #vis fn build(self) -> #ident #ty_generics {
+                #ident {
+                    #(#field_build),*
+                }
+            }
+        }
+    }
 }
 
 pub fn partial(input: TokenStream) -> TokenStream {
-    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    let derive_input = syn::parse_macro_input!(input as syn::DeriveInput);
 
-    let input = match PartialInput::from_derive_input(&input) {
+    let input = match PartialInput::from_derive_input(&derive_input) {
         Ok(input) => input,
         Err(err) => {
             return TokenStream::from(err.write_errors());
         }
     };
 
-    let derive_attr = input.args.derive.as_ref().map(|derives| {
+    if input.args.ident.is_none() && input.targets.is_empty() {
+        return TokenStream::from(darling::Error::missing_field("ident").write_errors());
+    }
+
+    let mut errors = darling::Error::accumulator();
+
+    if input.args.ident.is_none() {
+        errors.handle(check_container_options(
+            "partial",
+            &derive_input.attrs,
+            &input.args.given_options(),
+        ));
+    }
+
+    let generated_idents = std::iter::once(&input.args)
+        .chain(input.targets.iter())
+        .filter_map(|args| args.ident.as_ref())
+        .collect::<Vec<_>>();
+    // The other types generated along with the partial structs by their options, where only the
+    // first builder counts as the others are rejected below
+    let derived_idents = std::iter::once(&input.args)
+        .chain(input.targets.iter())
+        .filter_map(|args| Some((args, args.ident.as_ref()?)))
+        .flat_map(|(args, partial_ident)| {
+            [
+                args.try_from.then(|| {
+                    (
+                        "try_from",
+                        partial_ident,
+                        format_ident!("{}Error", partial_ident),
+                    )
+                }),
+                args.patch_ident(partial_ident)
+                    .map(|patch_ident| ("merge_patch", partial_ident, patch_ident)),
+            ]
+        })
+        .flatten()
+        .chain(
+            std::iter::once(&input.args)
+                .chain(input.targets.iter())
+                .filter_map(|args| Some((args, args.ident.as_ref()?)))
+                .find_map(|(args, partial_ident)| {
+                    Some(("builder", partial_ident, args.builder_ident(&input.ident)?))
+                }),
+        )
+        .collect::<Vec<_>>();
+    errors.handle(check_generated_idents(
+        "struct",
+        &input.ident,
+        generated_idents.iter().copied(),
+        derived_idents
+            .iter()
+            .map(|(option, partial_ident, ident)| (*option, *partial_ident, ident)),
+    ));
+    // Every builder is constructed by `Foo::builder()`, so only one of the partials may have one
+    for args in std::iter::once(&input.args)
+        .chain(input.targets.iter())
+        .filter(|args| args.builder.is_some())
+        .skip(1)
+    {
+        let error =
+            darling::Error::custom("`builder` can only be generated for one partial struct");
+        errors.push(match &args.ident {
+            Some(ident) => error.with_span(ident),
+            None => error,
+        });
+    }
+    let generated_names = generated_idents
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    if let Some(fields) = input.data.as_ref().take_struct() {
+        for field in fields.iter() {
+            errors.handle(check_names(
+                "generated struct",
+                field
+                    .args
+                    .iter()
+                    .map(|arg| (arg.ident.to_string(), &arg.ident)),
+                &generated_names,
+            ));
+        }
+    }
+
+    let partials = std::iter::once(&input.args)
+        .filter(|args| args.ident.is_some())
+        .chain(input.targets.iter())
+        .filter_map(|args| match &args.ident {
+            Some(partial_ident) => errors.handle(expand(&input, args, partial_ident)),
+            None => {
+                errors.push(darling::Error::missing_field("ident"));
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if let Err(err) = errors.finish() {
+        return TokenStream::from(err.write_errors());
+    }
+
+    quote! {
+        #(#partials)*
+    }
+    .into()
+}
+
+/// Generates the partial struct described by `args` and its implementations.
+fn expand(
+    input: &PartialInput,
+    args: &PartialArgs,
+    partial_ident: &Ident,
+) -> darling::Result<proc_macro2::TokenStream> {
+    let derive_attr = args.derive.as_ref().map(|derives| {
         let derives = derives.iter();
         quote! {
             #[derive(#(#derives),*)]
         }
     });
 
-    let forward_attrs = filter_forward_attrs(input.attrs.iter(), &input.args.forward_attrs);
+    // The container level `forward_attrs` also applies to the structs given by `arg(...)`
+    let container_forward_attrs = &args.forward_attrs + &input.args.forward_attrs;
+    let forward_attrs = filter_forward_attrs(input.attrs.iter(), container_forward_attrs);
 
-    let vis = input.vis;
-    let ident = input.ident;
-    let partial_ident = input.args.ident;
-    let generics = input.generics;
-    let fields = input.data.take_struct().unwrap();
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let fields = input.data.as_ref().take_struct().unwrap();
+
+    let mut errors = darling::Error::accumulator();
+
+    let merge_patch = args
+        .merge_patch
+        .clone()
+        .map(|merge_patch| merge_patch.unwrap_or_default());
+    if merge_patch.is_some() && args.flatten_option {
+        errors.push(
+            darling::Error::custom("`merge_patch` cannot be used with `flatten_option`")
+                .with_span(partial_ident),
+        );
+    }
+    let patch_ident = args.patch_ident(partial_ident);
+
+    if let Some(selected) = &args.fields {
+        let members = fields
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| field_member(field.ident.as_ref(), index))
+            .collect::<Vec<_>>();
+        errors.handle(selected.check(&members));
+    }
 
-    let mut field_idents = Vec::new();
     let mut field_declares = Vec::new();
+    let mut field_from_original = Vec::new();
     let mut field_from_partial = Vec::new();
+    let mut field_apply = Vec::new();
+    let mut field_merge = Vec::new();
+    let mut field_names = Vec::new();
+    let mut field_is_set = Vec::new();
+    let mut field_diff = Vec::new();
+    let mut field_try_from_partial = Vec::new();
+    let mut field_empty = Vec::new();
+    let mut field_setters = Vec::new();
+    let mut builder_declares = Vec::new();
+    let mut builder_to_partial = Vec::new();
+    let mut builder_try_from = Vec::new();
+    // Whether a kept field has no `default`, so the plain builder cannot always make a partial
+    // struct, where `builder_to_partial` is then incomplete
+    let mut keep_without_default = false;
+    let mut builder_fields = Vec::new();
+    let mut partial_types = Vec::new();
+
+    let builder = args
+        .builder
+        .clone()
+        .map(|builder| builder.unwrap_or_default());
+    if builder.is_some() && fields.style != Style::Struct {
+        errors.push(
+            darling::Error::custom("`builder` is only supported for structs with named fields")
+                .with_span(partial_ident),
+        );
+    }
 
-    fields.fields.iter().for_each(|field| {
+    for (index, field) in fields.fields.iter().enumerate() {
+        let options = field.options(partial_ident);
         let vis = &field.vis;
-        let ident = field.ident.as_ref().unwrap();
+        let member = field_member(field.ident.as_ref(), index);
+        let name = field.ident.as_ref().map(|ident| quote! { #ident: });
+
+        if options.skip.unwrap_or_default() {
+            let value = match &options.default {
+                Some(default) => quote! { #default },
+                None => quote! { core::default::Default::default() },
+            };
+            field_from_partial.push(quote! { #member: #value });
+            field_try_from_partial.push((member.clone(), value.clone(), false));
+            builder_try_from.push((member, value.clone(), false));
+            if let Some(ident) = &field.ident {
+                builder_fields.push(BuilderField {
+                    ident,
+                    ty: &field.ty,
+                    role: BuilderFieldRole::Skip(value),
+                });
+            }
+            continue;
+        }
+
+        // The position of the field may differ in the partial tuple struct as fields are skipped
+        let partial_member = field_member(field.ident.as_ref(), field_declares.len());
 
         let forward_attrs = filter_forward_attrs(
             field.attrs.iter(),
-            &field.forward_attrs + &input.args.forward_attrs,
+            &options.forward_attrs + container_forward_attrs,
         );
 
         let ty = &field.ty;
-        // TODO: It may be better to keep the original type if it is already an Option
-        let ty = quote! { Option<#ty> };
+        let kind = match field_kind(args, &options, &member, ty) {
+            Ok(kind) => kind,
+            Err(err) => {
+                errors.push(err);
+                continue;
+            }
+        };
+        // A flattened field holds `None` both when it is unchanged and when it is cleared
+        if args.diff && matches!(kind, FieldKind::Flatten) {
+            errors.push(
+                darling::Error::custom(
+                    "`diff` cannot tell a flattened field set to `None` from an unchanged one, use `merge_patch` instead of `flatten_option`",
+                )
+                .with_span(&member),
+            );
+        }
+
+        // With serde glue, only the fields set in a patch are serialized
+        let merge_patch_serde = merge_patch
+            .as_ref()
+            .is_some_and(|merge_patch| merge_patch.serde);
+        let serde_attr = match kind {
+            FieldKind::Patch(_) if merge_patch_serde => {
+                let skip = format!("{}::is_unchanged", patch_ident.as_ref().unwrap());
+                Some(quote! { #[serde(default, skip_serializing_if = #skip)] })
+            }
+            FieldKind::Plain | FieldKind::Nested(_) if merge_patch_serde => {
+                Some(quote! { #[serde(default, skip_serializing_if = "Option::is_none")] })
+            }
+            _ => None,
+        };
 
-        field_idents.push(ident.clone());
-        field_declares.push(quote! {
-            #(#forward_attrs)*
-            #vis #ident: #ty
+        partial_types.push(partial_type(kind, ty));
+        field_declares.push(match kind {
+            FieldKind::Plain => quote! {
+                #(#forward_attrs)*
+                #serde_attr
+                #vis #name Option<#ty>
+            },
+            FieldKind::Flatten | FieldKind::Keep => quote! {
+                #(#forward_attrs)*
+                #vis #name #ty
+            },
+            FieldKind::Nested(nested) => quote! {
+                #(#forward_attrs)*
+                #serde_attr
+                #vis #name Option<#nested>
+            },
+            FieldKind::Patch(inner) => quote! {
+                #(#forward_attrs)*
+                #serde_attr
+                #vis #name #patch_ident<#inner>
+            },
+        });
+        let value = from_original(kind, quote! { src.#member });
+        field_from_original.push(quote! { #partial_member: #value });
+        field_from_partial.push(match (kind, &options.default) {
+            (FieldKind::Plain, Some(default)) => quote! {
+                #member: src.#partial_member.unwrap_or(#default)
+            },
+            (FieldKind::Plain, None) => quote! {
+                #member: src.#partial_member.unwrap_or_default()
+            },
+            (FieldKind::Flatten, Some(default)) => quote! {
+                #member: src.#partial_member.or(#default)
+            },
+            (FieldKind::Flatten, None) | (FieldKind::Keep, _) => quote! {
+                #member: src.#partial_member
+            },
+            (FieldKind::Nested(_), Some(default)) => quote! {
+                #member: src.#partial_member.map(core::convert::Into::into).unwrap_or(#default)
+            },
+            (FieldKind::Nested(_), None) => quote! {
+                #member: src.#partial_member.map(core::convert::Into::into).unwrap_or_default()
+            },
+            (FieldKind::Patch(_), Some(default)) => quote! {
+                #member: match src.#partial_member {
+                    #patch_ident::Unchanged => #default,
+                    #patch_ident::Clear => None,
+                    #patch_ident::Set(value) => Some(value),
+                }
+            },
+            (FieldKind::Patch(_), None) => quote! {
+                #member: match src.#partial_member {
+                    #patch_ident::Unchanged | #patch_ident::Clear => None,
+                    #patch_ident::Set(value) => Some(value),
+                }
+            },
+        });
+        field_apply.push(match kind {
+            FieldKind::Plain => quote! {
+                if let Some(value) = self.#partial_member {
+                    target.#member = value;
+                }
+            },
+            FieldKind::Flatten => quote! {
+                if let Some(value) = self.#partial_member {
+                    target.#member = Some(value);
+                }
+            },
+            FieldKind::Nested(_) => quote! {
+                if let Some(value) = self.#partial_member {
+                    value.apply_to(&mut target.#member);
+                }
+            },
+            FieldKind::Patch(_) => quote! {
+                match self.#partial_member {
+                    #patch_ident::Unchanged => {}
+                    #patch_ident::Clear => target.#member = None,
+                    #patch_ident::Set(value) => target.#member = Some(value),
+                }
+            },
+            FieldKind::Keep => quote! {
+                target.#member = self.#partial_member;
+            },
+        });
+        field_names.push(member_name(&member));
+        // A kept field always holds a value, so only the optional fields count as set
+        match kind {
+            FieldKind::Keep => {}
+            FieldKind::Patch(_) => field_is_set.push((
+                member_name(&member),
+                quote! { !self.#partial_member.is_unchanged() },
+            )),
+            _ => field_is_set.push((
+                member_name(&member),
+                quote! { self.#partial_member.is_some() },
+            )),
+        }
+        field_merge.push(match kind {
+            FieldKind::Plain | FieldKind::Flatten => quote! {
+                #partial_member: higher.#partial_member.or(self.#partial_member)
+            },
+            FieldKind::Nested(_) => quote! {
+                #partial_member: match (self.#partial_member, higher.#partial_member) {
+                    (Some(lower), Some(higher)) => Some(lower.merge(higher)),
+                    (lower, higher) => higher.or(lower),
+                }
+            },
+            FieldKind::Patch(_) => quote! {
+                #partial_member: if higher.#partial_member.is_unchanged() {
+                    self.#partial_member
+                } else {
+                    higher.#partial_member
+                }
+            },
+            FieldKind::Keep => quote! {
+                #partial_member: higher.#partial_member
+            },
         });
-        field_from_partial.push(match &field.default {
-            Some(default) => quote! {
-                #ident: src.#ident.unwrap_or(#default)
+        field_diff.push(match kind {
+            FieldKind::Plain => quote! {
+                #partial_member: if old.#member != new.#member {
+                    Some(new.#member.clone())
+                } else {
+                    None
+                }
+            },
+            FieldKind::Flatten => quote! {
+                #partial_member: if old.#member != new.#member {
+                    new.#member.clone()
+                } else {
+                    None
+                }
+            },
+            FieldKind::Nested(nested) => quote! {
+                #partial_member: if old.#member != new.#member {
+                    Some(<#nested>::diff(&old.#member, &new.#member))
+                } else {
+                    None
+                }
             },
-            None => quote! {
-                #ident: src.#ident.unwrap_or_default()
+            FieldKind::Patch(_) => quote! {
+                #partial_member: if old.#member != new.#member {
+                    core::convert::From::from(new.#member.clone())
+                } else {
+                    #patch_ident::Unchanged
+                }
+            },
+            FieldKind::Keep => quote! {
+                #partial_member: new.#member.clone()
+            },
+        });
+
+        // Fields without a fallback are checked in the generated `TryFrom<Partial>`
+        let checked = matches!(
+            (kind, &options.default),
+            (FieldKind::Plain, None) | (FieldKind::Nested(_), _)
+        );
+        let value = match (kind, &options.default) {
+            (FieldKind::Plain, Some(default)) => quote! {
+                src.#partial_member.unwrap_or(#default)
+            },
+            (FieldKind::Flatten, Some(default)) => quote! {
+                src.#partial_member.or(#default)
+            },
+            (FieldKind::Plain | FieldKind::Flatten | FieldKind::Keep, _) => quote! {
+                src.#partial_member
+            },
+            // A nested partial which fails to convert is reported as missing as a whole
+            (FieldKind::Nested(_), Some(default)) => quote! {
+                src.#partial_member.map_or_else(
+                    || Some(#default),
+                    |value| core::convert::TryFrom::try_from(value).ok(),
+                )
+            },
+            (FieldKind::Nested(_), None) => quote! {
+                src.#partial_member
+                    .and_then(|value| core::convert::TryFrom::try_from(value).ok())
+            },
+            (FieldKind::Patch(_), Some(default)) => quote! {
+                match src.#partial_member {
+                    #patch_ident::Unchanged => #default,
+                    #patch_ident::Clear => None,
+                    #patch_ident::Set(value) => Some(value),
+                }
+            },
+            (FieldKind::Patch(_), None) => quote! {
+                match src.#partial_member {
+                    #patch_ident::Unchanged | #patch_ident::Clear => None,
+                    #patch_ident::Set(value) => Some(value),
+                }
             },
+        };
+        field_try_from_partial.push((member.clone(), value.clone(), checked));
+        let required = checked || matches!((kind, &options.default), (FieldKind::Keep, None));
+
+        // The plain builder holds the fields as in the partial struct, except for the kept fields
+        // which are `None` until set, so that a missing one is not silently filled with a value
+        match (kind, &options.default) {
+            (FieldKind::Keep, None) => {
+                keep_without_default = true;
+                // Without `try_from`, the plain builder could only fill the field if it is not set
+                if builder.as_ref().is_some_and(|builder| !builder.typestate) && !args.try_from {
+                    errors.push(
+                        darling::Error::custom(format!(
+                            "`builder` cannot fill the kept field `{}` if it is not set, give it a `default` or use `try_from` or `builder(typestate)`",
+                            member_name(&member)
+                        ))
+                        .with_span(&member),
+                    );
+                }
+            }
+            (FieldKind::Keep, Some(default)) => builder_to_partial.push(quote! {
+                #partial_member: src.#partial_member.unwrap_or_else(|| #default)
+            }),
+            _ => builder_to_partial.push(quote! { #partial_member: src.#partial_member }),
+        }
+        builder_try_from.push(match (kind, &options.default) {
+            (FieldKind::Keep, Some(default)) => (
+                member,
+                quote! { src.#partial_member.unwrap_or_else(|| #default) },
+                false,
+            ),
+            (FieldKind::Keep, None) => (member, quote! { src.#partial_member }, true),
+            _ => (member, value, checked),
+        });
+        builder_declares.push(match kind {
+            FieldKind::Plain | FieldKind::Keep => quote! { #partial_member: Option<#ty> },
+            FieldKind::Flatten => quote! { #partial_member: #ty },
+            FieldKind::Nested(nested) => quote! { #partial_member: Option<#nested> },
+            FieldKind::Patch(inner) => quote! { #partial_member: #patch_ident<#inner> },
         });
+        field_empty.push(match kind {
+            FieldKind::Patch(_) => quote! { #partial_member: #patch_ident::Unchanged },
+            _ => quote! { #partial_member: None },
+        });
+
+        if let Some(ident) = &field.ident {
+            let value = match kind {
+                FieldKind::Keep => quote! { Some(value) },
+                _ => from_original(kind, quote! { value }),
+            };
+            let doc = format!("Sets the `{}` field.", ident.unraw());
+            field_setters.push(quote! {
+                #[doc = #doc]
+                #vis fn #ident(mut self, value: #ty) -> Self {
+                    self.#partial_member = #value;
+                    self
+                }
+            });
+
+            builder_fields.push(BuilderField {
+                ident,
+                ty,
+                role: match (&options.default, required) {
+                    (Some(default), _) => BuilderFieldRole::Optional(quote! { #default }),
+                    (None, true) => BuilderFieldRole::Required,
+                    (None, false) => {
+                        BuilderFieldRole::Optional(quote! { core::default::Default::default() })
+                    }
+                },
+            });
+        }
+    }
+
+    errors.finish()?;
+
+    // The partial struct only keeps the generic parameters used by its fields, e.g. a type
+    // parameter only used by skipped fields is declared by the methods taking the original struct
+    let partial_generics = used_generics(generics, &partial_types);
+    let (partial_impl_generics, partial_ty_generics, partial_where_clause) =
+        partial_generics.split_for_impl();
+    let dropped_generics = dropped_generics(generics, &partial_generics);
+    let (method_generics, _, method_where_clause) = dropped_generics.split_for_impl();
+
+    let from_partial = if args.try_from {
+        let error_ident = format_ident!("{}Error", partial_ident);
+        let error = missing_fields_error(vis, &error_ident, partial_ident);
+        let body = try_from_body(&error_ident, quote! { Self }, field_try_from_partial);
+
+        quote! {
+            #error
+
+            impl #impl_generics core::convert::TryFrom<#partial_ident #partial_ty_generics> for #ident #ty_generics #where_clause {
+                type Error = #error_ident;
+
+                fn try_from(src: #partial_ident #partial_ty_generics) -> Result<Self, Self::Error> {
+                    #body
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl #impl_generics core::convert::From<#partial_ident #partial_ty_generics> for #ident #ty_generics #where_clause {
+                fn from(src: #partial_ident #partial_ty_generics) -> Self {
+                    Self {
+                        #(#field_from_partial),*
+                    }
+                }
+            }
+        }
+    };
+
+    let struct_body = struct_body(fields.style, partial_where_clause, &field_declares);
+    let (set_names, field_is_set): (Vec<_>, Vec<_>) = field_is_set.into_iter().unzip();
+
+    let patch_enum = merge_patch.as_ref().map(|merge_patch| {
+        let patch_ident = patch_ident.as_ref().unwrap();
+        let doc = format!(
+            "A field of `{partial_ident}` which distinguishes leaving the value unchanged from clearing it."
+        );
+
+        let serde_impls = merge_patch.serde.then(|| {
+            quote! {
+                impl<'de, T: ::serde::Deserialize<'de>> ::serde::Deserialize<'de> for #patch_ident<T> {
+                    fn deserialize<D: ::serde::Deserializer<'de>>(
+                        deserializer: D,
+                    ) -> Result<Self, D::Error> {
+                        <Option<T> as ::serde::Deserialize<'de>>::deserialize(deserializer)
+                            .map(core::convert::From::from)
+                    }
+                }
+
+                impl<T: ::serde::Serialize> ::serde::Serialize for #patch_ident<T> {
+                    fn serialize<S: ::serde::Serializer>(
+                        &self,
+                        serializer: S,
+                    ) -> Result<S::Ok, S::Error> {
+                        match self {
+                            Self::Set(value) => serializer.serialize_some(value),
+                            _ => serializer.serialize_none(),
+                        }
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #[doc = #doc]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+            #vis enum #patch_ident<T> {
+                /// Leave the field unchanged.
+                #[default]
+                Unchanged,
+                /// Clear the field, i.e. set it to `None`.
+                Clear,
+                /// Set the field to the value.
+                Set(T),
+            }
+
+            impl<T> #patch_ident<T> {
+                /// Returns `true` if the field is left unchanged.
+                #vis fn is_unchanged(&self) -> bool {
+                    matches!(self, Self::Unchanged)
+                }
+            }
+
+            impl<T> core::convert::From<Option<T>> for #patch_ident<T> {
+                fn from(value: Option<T>) -> Self {
+                    match value {
+                        Some(value) => Self::Set(value),
+                        None => Self::Clear,
+                    }
+                }
+            }
+
+            #serde_impls
+        }
     });
 
-    quote! {
+    let builder = builder.map(|builder| {
+        let builder_ident = args.builder_ident(ident).unwrap();
+        if builder.typestate {
+            typestate_builder(vis, ident, generics, &builder_ident, &builder_fields)
+        } else {
+            let build = if args.try_from {
+                let error_ident = format_ident!("{}Error", partial_ident);
+                let body = try_from_body(&error_ident, quote! { #ident }, builder_try_from);
+                quote! {
+                    /// Builds the value, failing if any required field is not set.
+                    #vis fn build(self) -> Result<#ident #ty_generics, #error_ident> {
+                        let src = self;
+                        #body
+                    }
+                }
+            } else {
+                quote! {
+                    /// Builds the value, filling the fields which are not set with their default.
+                    #vis fn build(self) -> #ident #ty_generics {
+                        let src = self;
+                        core::convert::From::from(#partial_ident {
+                            #(#builder_to_partial),*
+                        })
+                    }
+                }
+            };
+            // A kept field without `default` which is not set has no value in the partial struct
+            let into_partial = (!keep_without_default).then(|| {
+                quote! {
+                    impl #impl_generics core::convert::From<#builder_ident #ty_generics> for #partial_ident #partial_ty_generics #where_clause {
+                        fn from(src: #builder_ident #ty_generics) -> Self {
+                            Self {
+                                #(#builder_to_partial),*
+                            }
+                        }
+                    }
+                }
+            });
+            let doc = format!("A builder for `{ident}`, collecting the fields as in `{partial_ident}`.");
+
+            quote! {
+                #[doc = #doc]
+                #vis struct #builder_ident #impl_generics #where_clause {
+                    #(#builder_declares,)*
+                    __marker: core::marker::PhantomData<fn() -> #ident #ty_generics>,
+                }
+
+                impl #impl_generics #ident #ty_generics #where_clause {
+                    /// Constructs a builder with no field set.
+                    #vis fn builder() -> #builder_ident #ty_generics {
+                        #builder_ident {
+                            #(#field_empty,)*
+                            __marker: core::marker::PhantomData,
+                        }
+                    }
+                }
+
+                impl #impl_generics #builder_ident #ty_generics #where_clause {
+                    #(#field_setters)*
+
+                    #build
+                }
+
+                #into_partial
+            }
+        }
+    });
+
+    let diff = args.diff.then(|| {
+        quote! {
+            /// Constructs a partial containing the fields of `new` which differ from `old`.
+            #vis fn diff #method_generics (old: &#ident #ty_generics, new: &#ident #ty_generics) -> Self #method_where_clause {
+                Self {
+                    #(#field_diff),*
+                }
+            }
+        }
+    });
+
+    let merge_trait = args.merge_trait.as_ref().map(|merge_trait| {
+        quote! {
+            impl #partial_impl_generics #merge_trait for #partial_ident #partial_ty_generics #partial_where_clause {
+                fn merge(self, higher: Self) -> Self {
+                    #partial_ident::merge(self, higher)
+                }
+            }
+        }
+    });
+
+    Ok(quote! {
+        #patch_enum
+
         #derive_attr
         #(#forward_attrs)*
-        #vis struct #partial_ident #generics {
-            #(#field_declares),*
-        }
+        #vis struct #partial_ident #partial_generics #struct_body
+
+        impl #partial_impl_generics #partial_ident #partial_ty_generics #partial_where_clause {
+            /// Overwrites the fields of `target` which are set in this partial,
+            /// leaving the other fields unchanged.
+            #vis fn apply_to #method_generics (self, target: &mut #ident #ty_generics) #method_where_clause {
+                #(#field_apply)*
+            }
 
-        impl #generics core::convert::From<#ident #generics> for #partial_ident #generics {
-            fn from(src: #ident #generics) -> Self {
+            /// Merges two partials field by field, where the fields set in `higher` win.
+            #vis fn merge(self, higher: Self) -> Self {
                 Self {
-                    #(#field_idents: Some(src.#field_idents)),*
+                    #(#field_merge),*
                 }
             }
+
+            /// The names of the fields of the original struct kept in this partial.
+            #vis const FIELD_NAMES: &'static [&'static str] = &[#(#field_names),*];
+
+            /// Returns `true` if no optional field is set.
+            #vis fn is_empty(&self) -> bool {
+                true #(&& !(#field_is_set))*
+            }
+
+            /// Returns the number of optional fields which are set.
+            #vis fn len(&self) -> usize {
+                0 #(+ usize::from(#field_is_set))*
+            }
+
+            /// Returns the names of the optional fields which are set.
+            #vis fn set_field_names(&self) -> Vec<&'static str> {
+                let mut names = Vec::new();
+                #(
+                    if #field_is_set {
+                        names.push(#set_names);
+                    }
+                )*
+                names
+            }
+
+            /// Resolves the value by applying this partial onto `base`.
+            #vis fn resolve #method_generics (self, mut base: #ident #ty_generics) -> #ident #ty_generics #method_where_clause {
+                self.apply_to(&mut base);
+                base
+            }
+
+            #diff
         }
 
-        impl #generics core::convert::From<#partial_ident #generics> for #ident #generics {
-            fn from(src: #partial_ident #generics) -> Self {
+        impl #impl_generics core::convert::From<#ident #ty_generics> for #partial_ident #partial_ty_generics #where_clause {
+            fn from(src: #ident #ty_generics) -> Self {
                 Self {
-                    #(#field_from_partial),*
+                    #(#field_from_original),*
                 }
             }
         }
-    }
-    .into()
+
+        #from_partial
+
+        #builder
+
+        #merge_trait
+    })
 }
@@ -1,23 +1,169 @@
-use darling::ast::Data;
+use std::ops::Deref;
+
+use darling::ast::{Data, NestedMeta};
 use darling::util::{Ignored, PathList};
 use darling::{FromDeriveInput, FromField, FromMeta};
 use proc_macro::TokenStream;
-use quote::quote;
-use syn::{
-    AngleBracketedGenericArguments, Attribute, GenericArgument, Generics, Ident, PathArguments,
-    Type, TypePath, Visibility,
+use quote::{format_ident, quote};
+use syn::{Attribute, Generics, Ident, Meta, Type, Visibility};
+
+use crate::partial::{partial_fields, PartialFieldShape};
+
+use crate::utils::{
+    check_container_options, check_generated_idents, check_names, field_member,
+    filter_forward_attrs, missing_fields_error, option_inner_type, path_to_string, struct_body,
+    try_from_body, wrapped_inner_type, ForwardAttrsFilter, MemberList,
 };
 
-use crate::utils::{filter_forward_attrs, ForwardAttrsFilter};
+/// A wrapper type which is unwrapped like `Option`
+#[derive(Debug, Clone)]
+struct Wrapper {
+    path: syn::Path,
+
+    /// A function converting the wrapper into `Option<T>`, `Into<Option<T>>` is used by default
+    unwrap: Option<syn::Expr>,
+}
+
+/// A list of wrapper types, given either by path (`Maybe`) or with the unwrapping function
+/// (`MaybeUndefined = MaybeUndefined::into_option`)
+#[derive(Debug, Default, Clone)]
+struct WrapperList(Vec<Wrapper>);
+
+impl Deref for WrapperList {
+    type Target = Vec<Wrapper>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromMeta for WrapperList {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let mut errors = darling::Error::accumulator();
+        let values = items
+            .iter()
+            .filter_map(|item| {
+                errors.handle(match item {
+                    NestedMeta::Meta(Meta::Path(path)) => Ok(Wrapper {
+                        path: path.clone(),
+                        unwrap: None,
+                    }),
+                    NestedMeta::Meta(Meta::NameValue(name_value)) => Ok(Wrapper {
+                        path: name_value.path.clone(),
+                        unwrap: Some(name_value.value.clone()),
+                    }),
+                    _ => Err(
+                        darling::Error::unexpected_type("non path, expected wrapper type")
+                            .with_span(item),
+                    ),
+                })
+            })
+            .collect::<Vec<_>>();
+        errors.finish()?;
+
+        if values.is_empty() {
+            return Err(darling::Error::too_few_items(1));
+        }
+
+        Ok(Self(values))
+    }
+}
 
 #[derive(Debug, FromMeta)]
 struct RequiredArgs {
-    ident: Ident,
+    /// Optional at the container level, where the generated structs may be given by `arg(...)`
+    ident: Option<Ident>,
 
     derive: Option<PathList>,
 
     #[darling(default)]
     forward_attrs: ForwardAttrsFilter,
+
+    /// Only the given fields are unwrapped, the others keep their types
+    fields: Option<MemberList>,
+
+    /// Generate `TryFrom<Original>` reporting the fields which are `None`
+    #[darling(default)]
+    try_from: bool,
+
+    /// Wrapper types unwrapped in addition to `Option`
+    #[darling(default)]
+    unwrap: WrapperList,
+
+    /// Strip every optional layer instead of only the outermost one
+    #[darling(default)]
+    deep: bool,
+
+    /// Generate `TryFrom<Partial>` from the given struct derived by `Partial` on the same struct
+    try_from_partial: Option<syn::Path>,
+}
+
+impl RequiredArgs {
+    /// Returns the names of the options given besides `ident` and `forward_attrs`.
+    fn given_options(&self) -> Vec<&'static str> {
+        [
+            ("derive", self.derive.is_some()),
+            ("fields", self.fields.is_some()),
+            ("try_from", self.try_from),
+            ("unwrap", !self.unwrap.is_empty()),
+            ("deep", self.deep),
+            ("try_from_partial", self.try_from_partial.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(name, given)| given.then_some(name))
+        .collect()
+    }
+}
+
+#[derive(Debug, Default, Clone, FromMeta)]
+struct RequiredFieldOptions {
+    #[darling(default)]
+    forward_attrs: ForwardAttrsFilter,
+
+    /// Keep the type of the field instead of unwrapping it
+    keep: Option<bool>,
+
+    /// The value used by the generated `TryFrom<Original>` when the field is `None`
+    default: Option<syn::Expr>,
+
+    /// Wrapper types unwrapped in addition to the container level ones
+    #[darling(default)]
+    unwrap: WrapperList,
+
+    /// Overrides the container level `deep`
+    deep: Option<bool>,
+
+    /// The required type of the field, which should also derive `Required`
+    nested: Option<syn::Path>,
+}
+
+impl RequiredFieldOptions {
+    /// Overrides these options with the ones given for a specific generated struct.
+    fn with(&self, arg: &RequiredFieldOptions) -> RequiredFieldOptions {
+        RequiredFieldOptions {
+            forward_attrs: (&arg.forward_attrs + &self.forward_attrs).clone(),
+            keep: arg.keep.or(self.keep),
+            default: arg.default.clone().or_else(|| self.default.clone()),
+            unwrap: WrapperList(
+                arg.unwrap
+                    .iter()
+                    .chain(self.unwrap.iter())
+                    .cloned()
+                    .collect(),
+            ),
+            deep: arg.deep.or(self.deep),
+            nested: arg.nested.clone().or_else(|| self.nested.clone()),
+        }
+    }
+}
+
+/// Field options which only apply to the generated struct named by `ident`
+#[derive(Debug, FromMeta)]
+struct RequiredFieldArgs {
+    ident: Ident,
+
+    #[darling(flatten)]
+    options: RequiredFieldOptions,
 }
 
 #[derive(Debug, FromField)]
@@ -31,8 +177,21 @@ struct RequiredField {
 
     attrs: Vec<Attribute>,
 
-    #[darling(default)]
-    forward_attrs: ForwardAttrsFilter,
+    #[darling(flatten)]
+    options: RequiredFieldOptions,
+
+    #[darling(multiple, rename = "arg")]
+    args: Vec<RequiredFieldArgs>,
+}
+
+impl RequiredField {
+    /// Returns the options of the field for the generated struct named `required_ident`.
+    fn options(&self, required_ident: &Ident) -> RequiredFieldOptions {
+        match self.args.iter().find(|arg| arg.ident == *required_ident) {
+            Some(arg) => self.options.with(&arg.options),
+            None => self.options.clone(),
+        }
+    }
 }
 
 #[derive(Debug, FromDeriveInput)]
@@ -54,92 +213,474 @@ struct RequiredInput {
 
     #[darling(flatten)]
     args: RequiredArgs,
+
+    /// Args for each additional generated struct
+    #[darling(multiple, rename = "arg")]
+    targets: Vec<RequiredArgs>,
 }
 
 pub fn required(input: TokenStream) -> TokenStream {
-    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    let derive_input = syn::parse_macro_input!(input as syn::DeriveInput);
 
-    let input = match RequiredInput::from_derive_input(&input) {
+    let input = match RequiredInput::from_derive_input(&derive_input) {
         Ok(input) => input,
         Err(err) => {
             return TokenStream::from(err.write_errors());
         }
     };
 
-    let derive_attr = input.args.derive.as_ref().map(|derives| {
+    if input.args.ident.is_none() && input.targets.is_empty() {
+        return TokenStream::from(darling::Error::missing_field("ident").write_errors());
+    }
+
+    let mut errors = darling::Error::accumulator();
+
+    if input.args.ident.is_none() {
+        errors.handle(check_container_options(
+            "required",
+            &derive_input.attrs,
+            &input.args.given_options(),
+        ));
+    }
+
+    let generated_idents = std::iter::once(&input.args)
+        .chain(input.targets.iter())
+        .filter_map(|args| args.ident.as_ref())
+        .collect::<Vec<_>>();
+    // The error structs generated along with the required structs
+    let error_idents = std::iter::once(&input.args)
+        .chain(input.targets.iter())
+        .filter_map(|args| {
+            let option = match (args.try_from, &args.try_from_partial) {
+                (true, _) => "try_from",
+                (false, Some(_)) => "try_from_partial",
+                (false, None) => return None,
+            };
+            let required_ident = args.ident.as_ref()?;
+            Some((
+                option,
+                required_ident,
+                format_ident!("{}Error", required_ident),
+            ))
+        })
+        .collect::<Vec<_>>();
+    errors.handle(check_generated_idents(
+        "struct",
+        &input.ident,
+        generated_idents.iter().copied(),
+        error_idents
+            .iter()
+            .map(|(option, required_ident, ident)| (*option, *required_ident, ident)),
+    ));
+    let generated_names = generated_idents
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    if let Some(fields) = input.data.as_ref().take_struct() {
+        for field in fields.iter() {
+            errors.handle(check_names(
+                "generated struct",
+                field
+                    .args
+                    .iter()
+                    .map(|arg| (arg.ident.to_string(), &arg.ident)),
+                &generated_names,
+            ));
+        }
+    }
+
+    let requireds = std::iter::once(&input.args)
+        .filter(|args| args.ident.is_some())
+        .chain(input.targets.iter())
+        .filter_map(|args| match &args.ident {
+            Some(required_ident) => {
+                errors.handle(expand(&derive_input, &input, args, required_ident))
+            }
+            None => {
+                errors.push(darling::Error::missing_field("ident"));
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if let Err(err) = errors.finish() {
+        return TokenStream::from(err.write_errors());
+    }
+
+    quote! {
+        #(#requireds)*
+    }
+    .into()
+}
+
+/// Returns the type wrapped by `ty` and the wrapper, where `None` stands for `Option` itself.
+fn unwrap_layer<'t, 'w>(
+    ty: &'t Type,
+    wrappers: &[&'w Wrapper],
+) -> Option<(&'t Type, Option<&'w Wrapper>)> {
+    match option_inner_type(ty) {
+        Some(inner) => Some((inner, None)),
+        None => wrappers.iter().find_map(|wrapper| {
+            wrapped_inner_type(ty, &[&path_to_string(&wrapper.path)])
+                .map(|inner| (inner, Some(*wrapper)))
+        }),
+    }
+}
+
+/// How the value of a field is converted into its type in the required struct.
+struct FieldConversion<'a> {
+    /// The wrapper layers to unwrap from the outermost, where `None` stands for `Option` itself
+    layers: Vec<Option<Wrapper>>,
+
+    /// The type left after unwrapping
+    inner: &'a Type,
+
+    nested: Option<syn::Path>,
+
+    default: Option<syn::Expr>,
+}
+
+/// The value of a field in the source of a fallible conversion.
+enum SourceValue {
+    /// The value of the original field
+    Value(proc_macro2::TokenStream),
+    /// `Option` of the value of the original field, which is `None` if the field is missing
+    Optional(proc_macro2::TokenStream),
+    /// `Option` of the given partial type of the original field, which is `None` if the field is
+    /// missing
+    Partial(syn::Path, proc_macro2::TokenStream),
+}
+
+impl FieldConversion<'_> {
+    /// Returns `Option<T>` of the value of `start` unwrapped layer by layer, or `None` if there is
+    /// no layer to unwrap.
+    fn unwrap(&self, start: proc_macro2::TokenStream) -> Option<proc_macro2::TokenStream> {
+        let mut value: Option<proc_macro2::TokenStream> = None;
+        for wrapper in &self.layers {
+            value = Some(match (wrapper, value) {
+                (None, None) => start.clone(),
+                (None, Some(value)) => quote! { #value.flatten() },
+                (Some(wrapper), value) => {
+                    let arg = match &value {
+                        Some(_) => quote! { value },
+                        None => start.clone(),
+                    };
+                    let to_option = match &wrapper.unwrap {
+                        Some(unwrap) => quote! { (#unwrap)(#arg) },
+                        None => quote! { core::convert::Into::<Option<_>>::into(#arg) },
+                    };
+                    match value {
+                        Some(value) => quote! { #value.and_then(|value| #to_option) },
+                        None => to_option,
+                    }
+                }
+            });
+        }
+        value
+    }
+
+    /// Returns the expression converting `source` into the value of the required field, and
+    /// whether it is an `Option` which is `None` if the field is missing.
+    fn try_convert(&self, source: SourceValue) -> (proc_macro2::TokenStream, bool) {
+        let inner = self.inner;
+        let (value, start) = match source {
+            SourceValue::Value(start) => (self.unwrap(start.clone()), start),
+            SourceValue::Optional(value) => {
+                let value = match self.unwrap(quote! { value }) {
+                    Some(unwrap) => quote! { #value.and_then(|value| #unwrap) },
+                    None => value,
+                };
+                (Some(value), quote! {})
+            }
+            // The partial type is converted into the required type at once
+            SourceValue::Partial(partial, value) => {
+                let target = match &self.nested {
+                    Some(nested) => quote! { #nested },
+                    None => quote! { #inner },
+                };
+                let try_from = quote! { <#target as core::convert::TryFrom<#partial>>::try_from };
+                let value = match &self.default {
+                    Some(default) => quote! {
+                        #value.map_or_else(|| Some(#default), |value| #try_from(value).ok())
+                    },
+                    None => quote! { #value.and_then(|value| #try_from(value).ok()) },
+                };
+                return (value, true);
+            }
+        };
+
+        // A nested struct which fails to convert is reported as missing as a whole, even if the
+        // field has a `default` for when it is `None`
+        match (&self.nested, value, &self.default) {
+            (Some(nested), value, default) => {
+                let try_from = quote! { <#nested as core::convert::TryFrom<#inner>>::try_from };
+                let value = match (value, default) {
+                    (Some(value), Some(default)) => quote! {
+                        #value.map_or_else(|| Some(#default), |value| #try_from(value).ok())
+                    },
+                    (Some(value), None) => quote! {
+                        #value.and_then(|value| #try_from(value).ok())
+                    },
+                    (None, _) => quote! { #try_from(#start).ok() },
+                };
+                (value, true)
+            }
+            (None, Some(value), Some(default)) => {
+                (quote! { #value.unwrap_or_else(|| #default) }, false)
+            }
+            (None, Some(value), None) => (value, true),
+            (None, None, _) => (start, false),
+        }
+    }
+}
+
+/// Generates the required struct described by `args`.
+fn expand(
+    derive_input: &syn::DeriveInput,
+    input: &RequiredInput,
+    args: &RequiredArgs,
+    required_ident: &Ident,
+) -> darling::Result<proc_macro2::TokenStream> {
+    let derive_attr = args.derive.as_ref().map(|derives| {
         let derives = derives.iter();
         quote! {
             #[derive(#(#derives),*)]
         }
     });
 
-    let forward_attrs = filter_forward_attrs(input.attrs.iter(), &input.args.forward_attrs);
+    // The container level `forward_attrs` also applies to the structs given by `arg(...)`
+    let container_forward_attrs = &args.forward_attrs + &input.args.forward_attrs;
+    let forward_attrs = filter_forward_attrs(input.attrs.iter(), container_forward_attrs);
 
-    let vis = input.vis;
-    let _ident = input.ident;
-    let required_ident = input.args.ident;
-    let generics = input.generics;
-    let fields = input.data.take_struct().unwrap();
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let generics = &input.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let fields = input.data.as_ref().take_struct().unwrap();
+
+    let mut errors = darling::Error::accumulator();
+
+    if let Some(selected) = &args.fields {
+        let members = fields
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| field_member(field.ident.as_ref(), index))
+            .collect::<Vec<_>>();
+        errors.handle(selected.check(&members));
+    }
 
-    let mut field_idents = Vec::new();
     let mut field_declares = Vec::new();
+    let mut field_from_required = Vec::new();
+    let mut conversions = Vec::new();
 
-    fields.fields.iter().for_each(|field| {
+    for (index, field) in fields.fields.iter().enumerate() {
+        let options = field.options(required_ident);
+        let member = field_member(field.ident.as_ref(), index);
         let vis = &field.vis;
-        let ident = field.ident.as_ref().unwrap();
+        let name = field.ident.as_ref().map(|ident| quote! { #ident: });
 
         let forward_attrs = filter_forward_attrs(
             field.attrs.iter(),
-            &field.forward_attrs + &input.args.forward_attrs,
+            &options.forward_attrs + container_forward_attrs,
         );
 
         let ty = &field.ty;
 
-        // Check if the field is optional
-        let ty = match ty {
-            Type::Path(TypePath { path, .. }) => {
-                let segments_str = &path
-                    .segments
-                    .iter()
-                    .map(|segment| segment.ident.to_string())
-                    .collect::<Vec<_>>()
-                    .join("::");
-                let option_segment = ["Option", "std::option::Option", "core::option::Option"]
-                    .iter()
-                    .find(|s| segments_str == *s)
-                    .and_then(|_| path.segments.last());
-                let inner_type = option_segment
-                    .and_then(|path_seg| match &path_seg.arguments {
-                        PathArguments::AngleBracketed(AngleBracketedGenericArguments {
-                            args,
-                            ..
-                        }) => args.first(),
-                        _ => None,
-                    })
-                    .and_then(|generic_arg| match generic_arg {
-                        GenericArgument::Type(ty) => Some(ty),
-                        _ => None,
-                    });
-
-                inner_type.unwrap_or(ty)
+        // Fields not listed in the container level `fields` keep their types, whatever their other
+        // options, which may have been given for the other generated structs
+        let unselected = options.keep.is_none()
+            && args
+                .fields
+                .as_ref()
+                .is_some_and(|selected| !selected.contains(&member));
+        if unselected || options.keep.unwrap_or_default() {
+            if !unselected && options.nested.is_some() {
+                errors.push(
+                    darling::Error::custom("`nested` cannot be used with `keep`")
+                        .with_span(&member),
+                );
+                continue;
             }
-            _ => ty,
+            field_from_required.push(quote! { #member: src.#member });
+            field_declares.push(quote! {
+                #(#forward_attrs)*
+                #vis #name #ty
+            });
+            conversions.push((
+                member,
+                FieldConversion {
+                    layers: Vec::new(),
+                    inner: ty,
+                    nested: None,
+                    default: None,
+                },
+            ));
+            continue;
+        }
+
+        // The wrapper layers to unwrap from the outermost, where `None` stands for `Option` itself
+        let wrappers = options
+            .unwrap
+            .iter()
+            .chain(args.unwrap.iter())
+            .collect::<Vec<_>>();
+        let deep = options.deep.unwrap_or(args.deep);
+        let mut layers = Vec::new();
+        let mut inner = ty;
+        while let Some((next, wrapper)) = unwrap_layer(inner, &wrappers) {
+            layers.push((inner, wrapper));
+            inner = next;
+            if !deep {
+                break;
+            }
+        }
+
+        if layers.is_empty() && options.nested.is_none() {
+            if let Some(default) = &options.default {
+                errors.push(
+                    darling::Error::custom("`default` can only be used on `Option` fields")
+                        .with_span(default),
+                );
+                continue;
+            }
+        }
+
+        // Converts the value of the required field back, from the innermost layer
+        let mut wrap = match &options.nested {
+            Some(nested) => quote! { <#inner as core::convert::From<#nested>>::from(src.#member) },
+            None => quote! { src.#member },
+        };
+        for (layer_ty, wrapper) in layers.iter().rev() {
+            wrap = match wrapper {
+                None => quote! { Some(#wrap) },
+                Some(_) => quote! { <#layer_ty as core::convert::From<_>>::from(#wrap) },
+            };
+        }
+        field_from_required.push(quote! { #member: #wrap });
+
+        let ty = match &options.nested {
+            Some(nested) => quote! { #nested },
+            None => quote! { #inner },
         };
 
-        field_idents.push(ident.clone());
         field_declares.push(quote! {
             #(#forward_attrs)*
-            #vis #ident: #ty
+            #vis #name #ty
         });
+        conversions.push((
+            member,
+            FieldConversion {
+                layers: layers
+                    .into_iter()
+                    .map(|(_, wrapper)| wrapper.cloned())
+                    .collect(),
+                inner,
+                nested: options.nested,
+                default: options.default,
+            },
+        ));
+    }
+    errors.finish()?;
+
+    let struct_body = struct_body(fields.style, where_clause, &field_declares);
+
+    let error_ident = format_ident!("{}Error", required_ident);
+    let error = (args.try_from || args.try_from_partial.is_some())
+        .then(|| missing_fields_error(vis, &error_ident, ident));
+
+    let try_from = args.try_from.then(|| {
+        let values = conversions.iter().map(|(member, conversion)| {
+            conversion.try_convert(SourceValue::Value(quote! { src.#member }))
+        });
+        let body = try_from_body(
+            &error_ident,
+            quote! { Self },
+            conversions
+                .iter()
+                .zip(values)
+                .map(|((member, _), (value, checked))| (member.clone(), value, checked)),
+        );
+
+        quote! {
+            impl #impl_generics core::convert::TryFrom<#ident #ty_generics> for #required_ident #ty_generics #where_clause {
+                type Error = #error_ident;
+
+                fn try_from(src: #ident #ty_generics) -> Result<Self, Self::Error> {
+                    #body
+                }
+            }
+        }
     });
 
-    // TODO: Implement From<RequiredStruct> for OriginalStruct
-    quote! {
+    let try_from_partial = match &args.try_from_partial {
+        Some(partial) => {
+            let partial_ident = &partial.segments.last().unwrap().ident;
+            let (partial_generics, shapes) = partial_fields(derive_input, partial_ident)?;
+            let (_, partial_ty_generics, _) = partial_generics.split_for_impl();
+            let values = shapes.into_iter().zip(&conversions).map(
+                |((shape, partial_member), (_, conversion))| {
+                    let source = match shape {
+                        PartialFieldShape::Skip(value) => SourceValue::Value(value),
+                        PartialFieldShape::Plain => {
+                            SourceValue::Optional(quote! { src.#partial_member })
+                        }
+                        PartialFieldShape::Flatten | PartialFieldShape::Keep => {
+                            SourceValue::Value(quote! { src.#partial_member })
+                        }
+                        PartialFieldShape::Nested(nested) => {
+                            SourceValue::Partial(nested, quote! { src.#partial_member })
+                        }
+                        PartialFieldShape::Patch(patch_ident) => SourceValue::Optional(quote! {
+                            match src.#partial_member {
+                                #patch_ident::Unchanged => None,
+                                #patch_ident::Clear => Some(None),
+                                #patch_ident::Set(value) => Some(Some(value)),
+                            }
+                        }),
+                    };
+                    conversion.try_convert(source)
+                },
+            );
+            let body = try_from_body(
+                &error_ident,
+                quote! { Self },
+                conversions
+                    .iter()
+                    .zip(values)
+                    .map(|((member, _), (value, checked))| (member.clone(), value, checked)),
+            );
+
+            Some(quote! {
+                impl #impl_generics core::convert::TryFrom<#partial #partial_ty_generics> for #required_ident #ty_generics #where_clause {
+                    type Error = #error_ident;
+
+                    fn try_from(src: #partial #partial_ty_generics) -> Result<Self, Self::Error> {
+                        #body
+                    }
+                }
+            })
+        }
+        None => None,
+    };
+
+    Ok(quote! {
         #derive_attr
         #(#forward_attrs)*
-        #vis struct #required_ident #generics {
-            #(#field_declares),*
+        #vis struct #required_ident #generics #struct_body
+
+        impl #impl_generics core::convert::From<#required_ident #ty_generics> for #ident #ty_generics #where_clause {
+            fn from(src: #required_ident #ty_generics) -> Self {
+                Self {
+                    #(#field_from_required),*
+                }
+            }
         }
-    }
-    .into()
+
+        #error
+
+        #try_from
+
+        #try_from_partial
+    })
 }
@@ -1,9 +1,19 @@
+use std::collections::HashSet;
 use std::ops::{Add, Deref};
 
-use darling::ast::NestedMeta;
-use darling::util::PathList;
+use darling::ast::{NestedMeta, Style};
+use darling::util::{Override, PathList};
 use darling::FromMeta;
-use syn::{Attribute, Ident, Meta};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{
+    parse_quote, AngleBracketedGenericArguments, Attribute, GenericArgument, GenericParam,
+    Generics, Ident, Index, Lifetime, LifetimeParam, Lit, Member, Meta, Path, PathArguments, Type,
+    TypePath, Visibility, WhereClause, WherePredicate,
+};
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct IdentList(Vec<Ident>);
@@ -12,6 +22,15 @@ impl IdentList {
     pub fn new<T: Into<Ident>>(values: Vec<T>) -> Self {
         Self(values.into_iter().map(T::into).collect())
     }
+
+    /// Checks that every listed ident is one of `idents` and is listed only once.
+    pub fn check(&self, kind: &str, idents: &[&Ident]) -> darling::Result<()> {
+        check_names(
+            kind,
+            self.iter().map(|ident| (ident.to_string(), ident)),
+            &idents.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        )
+    }
 }
 
 impl Deref for IdentList {
@@ -30,18 +49,23 @@ impl From<Vec<Ident>> for IdentList {
 
 impl FromMeta for IdentList {
     fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let mut errors = darling::Error::accumulator();
         let values = items
             .iter()
-            .map(|item| match item {
-                NestedMeta::Meta(Meta::Path(path)) => match path.get_ident() {
-                    Some(ident) => Ok(ident.clone()),
-                    None => Err(darling::Error::unexpected_type("non ident").with_span(item)),
-                },
-                _ => {
-                    Err(darling::Error::unexpected_type("non path, expected ident").with_span(item))
-                }
+            .filter_map(|item| {
+                errors.handle(match item {
+                    NestedMeta::Meta(Meta::Path(path)) => match path.get_ident() {
+                        Some(ident) => Ok(ident.clone()),
+                        None => Err(darling::Error::unexpected_type("non ident").with_span(item)),
+                    },
+                    _ => {
+                        Err(darling::Error::unexpected_type("non path, expected ident")
+                            .with_span(item))
+                    }
+                })
             })
-            .collect::<darling::Result<Vec<Ident>>>()?;
+            .collect::<Vec<Ident>>();
+        errors.finish()?;
 
         if values.is_empty() {
             return Err(darling::Error::too_few_items(1));
@@ -51,6 +75,714 @@ impl FromMeta for IdentList {
     }
 }
 
+/// A list of struct fields, given either by name (`foo`) or by position (`0`)
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MemberList(Vec<Member>);
+
+impl Deref for MemberList {
+    type Target = Vec<Member>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromMeta for MemberList {
+    fn from_list(items: &[NestedMeta]) -> darling::Result<Self> {
+        let mut errors = darling::Error::accumulator();
+        let values = items
+            .iter()
+            .filter_map(|item| {
+                errors.handle(match item {
+                    NestedMeta::Meta(Meta::Path(path)) => match path.get_ident() {
+                        Some(ident) => Ok(Member::Named(ident.clone())),
+                        None => Err(darling::Error::unexpected_type("non ident").with_span(item)),
+                    },
+                    NestedMeta::Lit(Lit::Int(lit)) => lit
+                        .base10_parse()
+                        .map(|index| {
+                            Member::Unnamed(Index {
+                                index,
+                                span: lit.span(),
+                            })
+                        })
+                        .map_err(darling::Error::from),
+                    _ => Err(darling::Error::unexpected_type(
+                        "non path or integer, expected ident or index",
+                    )
+                    .with_span(item)),
+                })
+            })
+            .collect::<Vec<Member>>();
+        errors.finish()?;
+
+        if values.is_empty() {
+            return Err(darling::Error::too_few_items(1));
+        }
+
+        Ok(Self(values))
+    }
+}
+
+impl MemberList {
+    /// Checks that every listed member is one of `members`, the fields of the struct, and is
+    /// listed only once.
+    pub fn check(&self, members: &[Member]) -> darling::Result<()> {
+        check_names(
+            "field",
+            self.iter().map(|member| (member_name(member), member)),
+            &members.iter().map(member_name).collect::<Vec<_>>(),
+        )
+    }
+}
+
+/// Checks that the idents of the generated types differ from each other and from the original
+/// type `source`. Each is either given by an `arg`, or `(option, arg, ident)` derived from the
+/// ident of an `arg` by one of its options.
+pub fn check_generated_idents<'a>(
+    kind: &str,
+    source: &Ident,
+    idents: impl IntoIterator<Item = &'a Ident>,
+    derived: impl IntoIterator<Item = (&'a str, &'a Ident, &'a Ident)>,
+) -> darling::Result<()> {
+    let mut errors = darling::Error::accumulator();
+    let mut seen = HashSet::new();
+    let idents = idents.into_iter().map(|ident| (None, ident)).chain(
+        derived
+            .into_iter()
+            .map(|(option, arg, ident)| (Some((option, arg)), ident)),
+    );
+    for (origin, ident) in idents {
+        let message = match (origin, ident == source) {
+            (None, true) => {
+                format!("generated {kind} `{ident}` has the same name as the original {kind}")
+            }
+            (Some((option, arg)), true) => format!(
+                "`{option}` on `{arg}` generates `{ident}`, which has the same name as the original {kind}"
+            ),
+            (None, false) if !seen.insert(ident) => {
+                format!("another `arg` already generates `{ident}`")
+            }
+            (Some((option, arg)), false) if !seen.insert(ident) => format!(
+                "`{option}` on `{arg}` generates `{ident}`, which is also generated by another `arg`"
+            ),
+            _ => continue,
+        };
+        errors.push(darling::Error::custom(message).with_span(ident));
+    }
+    errors.finish()
+}
+
+/// Reports each of `options` given at the container level of the `#[<attr>(...)]` attributes without
+/// `ident`, where only `forward_attrs` is allowed as it is the only option inherited by each `arg`.
+pub fn check_container_options(
+    attr: &str,
+    attrs: &[Attribute],
+    options: &[&str],
+) -> darling::Result<()> {
+    let mut errors = darling::Error::accumulator();
+    for option in options {
+        let error = darling::Error::custom(format!(
+            "`{option}` requires `ident` at the container level, give it in each `arg(...)` instead"
+        ));
+        // Spanned at the option itself if it can be found among the attributes
+        let path = attrs
+            .iter()
+            .filter(|attribute| attribute.path().is_ident(attr))
+            .filter_map(|attribute| {
+                NestedMeta::parse_meta_list(attribute.meta.require_list().ok()?.tokens.clone()).ok()
+            })
+            .flatten()
+            .find_map(|item| match item {
+                NestedMeta::Meta(meta) if meta.path().is_ident(option) => Some(meta.path().clone()),
+                _ => None,
+            });
+        errors.push(match path {
+            Some(path) => error.with_span(&path),
+            None => error,
+        });
+    }
+    errors.finish()
+}
+
+/// Reports every listed name that is not in `known`, suggesting the closest known name, and
+/// every name listed more than once.
+pub fn check_names<'a, T: ToTokens + 'a>(
+    kind: &str,
+    listed: impl Iterator<Item = (String, &'a T)>,
+    known: &[String],
+) -> darling::Result<()> {
+    let mut errors = darling::Error::accumulator();
+    let mut seen = HashSet::new();
+    for (name, node) in listed {
+        if !known.contains(&name) {
+            let message = match closest_name(&name, known) {
+                Some(closest) => format!("unknown {kind} `{name}`, did you mean `{closest}`?"),
+                None => format!("unknown {kind} `{name}`"),
+            };
+            errors.push(darling::Error::custom(message).with_span(node));
+        } else if !seen.insert(name.clone()) {
+            errors
+                .push(darling::Error::custom(format!("duplicate {kind} `{name}`")).with_span(node));
+        }
+    }
+    errors.finish()
+}
+
+/// Returns the known name closest to `name` if it is close enough to be a typo. Field indices
+/// get no suggestion.
+fn closest_name<'a>(name: &str, known: &'a [String]) -> Option<&'a str> {
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    // Any single character name is one edit away from another, so those get no suggestion
+    let len = name.chars().count();
+    let max_distance = if len > 1 { (len / 3).max(1) } else { 0 };
+    known
+        .iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.as_str())
+}
+
+/// The edit distance between two strings, counting insertions, deletions, substitutions and
+/// transpositions of adjacent characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    (0..=a.len()).for_each(|i| distances[i][0] = i);
+    (0..=b.len()).for_each(|j| distances[0][j] = j);
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = substitution
+                .min(distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+/// Returns how the `index`-th field of a struct is accessed, i.e. `src.ident` or `src.0`.
+pub fn field_member(ident: Option<&Ident>, index: usize) -> Member {
+    match ident {
+        Some(ident) => Member::Named(ident.clone()),
+        None => Member::Unnamed(Index::from(index)),
+    }
+}
+
+/// Returns the name of a field as shown to users, i.e. `ident` or `0`.
+pub fn member_name(member: &Member) -> String {
+    match member {
+        Member::Named(ident) => ident.unraw().to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    }
+}
+
+/// Converts an `UpperCamelCase` identifier into `snake_case`, e.g. `PickAB` into `pick_ab`.
+pub fn to_snake_case(ident: &Ident) -> String {
+    let chars = ident.unraw().to_string().chars().collect::<Vec<_>>();
+    let mut snake = String::new();
+    for (index, char) in chars.iter().enumerate() {
+        if char.is_uppercase() && index > 0 {
+            let prev = chars[index - 1];
+            let next_lowercase = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_lowercase)
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(char.to_lowercase());
+    }
+    snake
+}
+
+/// The fields of the original struct which make up one generated struct.
+#[derive(Default)]
+pub struct FieldsPart<'a> {
+    /// How each field is accessed in the generated struct and in the original struct
+    pub members: Vec<(Member, Member)>,
+    /// The declaration of each field without its type, i.e. its attributes, visibility and name
+    pub heads: Vec<TokenStream>,
+    pub types: Vec<&'a Type>,
+}
+
+impl<'a> FieldsPart<'a> {
+    pub fn push(
+        &mut self,
+        ident: Option<&Ident>,
+        src_member: Member,
+        head: TokenStream,
+        ty: &'a Type,
+    ) {
+        self.members
+            .push((field_member(ident, self.members.len()), src_member));
+        self.heads.push(head);
+        self.types.push(ty);
+    }
+
+    /// Declarations of the fields, with each type wrapped by `wrap_type`.
+    pub fn declares(&self, wrap_type: impl Fn(&Type) -> TokenStream) -> Vec<TokenStream> {
+        self.heads
+            .iter()
+            .zip(&self.types)
+            .map(|(head, ty)| {
+                let ty = wrap_type(ty);
+                quote! { #head #ty }
+            })
+            .collect()
+    }
+
+    /// Initializers of the fields of the generated struct from the original struct `src`.
+    pub fn inits(&self) -> Vec<TokenStream> {
+        self.members
+            .iter()
+            .map(|(member, src_member)| quote! { #member: src.#src_member })
+            .collect()
+    }
+}
+
+/// Generates `split_<part>` on the original struct, which moves its fields into the generated
+/// struct `part` and the struct of the remaining fields `rest`, and `From<(Part, Rest)>` for the
+/// original struct which joins both back.
+pub fn split_impls(
+    vis: &Visibility,
+    ident: &Ident,
+    generics: &Generics,
+    (part_ident, part_generics, part): (&Ident, &Generics, &FieldsPart),
+    (rest_ident, rest_generics, rest): (&Ident, &Generics, &FieldsPart),
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (_, part_ty_generics, _) = part_generics.split_for_impl();
+    let (_, rest_ty_generics, _) = rest_generics.split_for_impl();
+
+    let split_ident = format_ident!("split_{}", to_snake_case(part_ident));
+    let doc = format!("Splits into the fields of `{part_ident}` and the remaining fields.");
+    let part_inits = part.inits();
+    let rest_inits = rest.inits();
+
+    let joins = part
+        .members
+        .iter()
+        .map(|(member, src_member)| quote! { #src_member: part.#member })
+        .chain(
+            rest.members
+                .iter()
+                .map(|(member, src_member)| quote! { #src_member: rest.#member }),
+        );
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc = #doc]
+            #vis fn #split_ident(self) -> (#part_ident #part_ty_generics, #rest_ident #rest_ty_generics) {
+                let src = self;
+                (
+                    #part_ident {
+                        #(#part_inits),*
+                    },
+                    #rest_ident {
+                        #(#rest_inits),*
+                    },
+                )
+            }
+        }
+
+        impl #impl_generics From<(#part_ident #part_ty_generics, #rest_ident #rest_ty_generics)> for #ident #ty_generics #where_clause {
+            fn from((part, rest): (#part_ident #part_ty_generics, #rest_ident #rest_ty_generics)) -> Self {
+                Self {
+                    #(#joins),*
+                }
+            }
+        }
+    }
+}
+
+/// Options of `split`, which also generates the struct of the remaining fields.
+#[derive(Debug, Default, Clone, FromMeta)]
+pub struct SplitArgs {
+    /// The identifier of the struct of the remaining fields
+    ident: Option<Ident>,
+}
+
+/// Returns the ident of the struct of the remaining fields if `split` is given, which defaults
+/// to `<IDENT>Rest`.
+pub fn rest_ident(ident: &Ident, split: Option<&Override<SplitArgs>>) -> Option<Ident> {
+    split.map(|split| {
+        split
+            .as_ref()
+            .explicit()
+            .and_then(|split| split.ident.clone())
+            .unwrap_or_else(|| format_ident!("{}Rest", ident))
+    })
+}
+
+/// Options of `ref_view` and `mut_view`, which generate a view struct borrowing the fields from the original
+/// struct.
+#[derive(Debug, Default, Clone, FromMeta)]
+pub struct ViewArgs {
+    /// The identifier of the view struct
+    ident: Option<Ident>,
+
+    /// Derive attributes for the view struct
+    derive: Option<PathList>,
+}
+
+/// A view struct borrowing some fields of the original struct, either shared or mutably.
+pub struct View<'a> {
+    pub ident: Ident,
+    pub args: &'a Override<ViewArgs>,
+    pub mutable: bool,
+}
+
+impl<'a> View<'a> {
+    /// Returns the view given by `ref_view` or `mut_view` of the generated struct `ident`, named
+    /// `<IDENT>Ref` or `<IDENT>Mut` unless given.
+    pub fn new(ident: &Ident, args: Option<&'a Override<ViewArgs>>, mutable: bool) -> Option<Self> {
+        args.map(|args| View {
+            ident: args
+                .as_ref()
+                .explicit()
+                .and_then(|args| args.ident.clone())
+                .unwrap_or_else(|| match mutable {
+                    true => format_ident!("{}Mut", ident),
+                    false => format_ident!("{}Ref", ident),
+                }),
+            args,
+            mutable,
+        })
+    }
+
+    /// Generates the view struct of the generated struct `part` and the `as_<part>` or
+    /// `as_<part>_mut` accessor on the original struct.
+    pub fn expand(
+        &self,
+        (vis, ident, generics): (&Visibility, &Ident, &Generics),
+        (part_ident, part_generics, part): (&Ident, &Generics, &FieldsPart),
+        style: Style,
+        forward_attrs: &[&Attribute],
+    ) -> TokenStream {
+        let view_ident = &self.ident;
+        let lifetime = Lifetime::new("'view", proc_macro2::Span::call_site());
+        let mutability = self.mutable.then(|| quote! { mut });
+
+        let derive_attr = self
+            .args
+            .as_ref()
+            .explicit()
+            .and_then(|args| args.derive.as_ref())
+            .map(|derives| {
+                let derives = derives.iter();
+                quote! {
+                    #[derive(#(#derives),*)]
+                }
+            });
+
+        let mut view_generics = part_generics.clone();
+        view_generics.params.insert(
+            0,
+            GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())),
+        );
+        let (_, view_ty_generics, view_where_clause) = view_generics.split_for_impl();
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+        let field_declares = part.declares(|ty| quote! { &#lifetime #mutability #ty });
+        let struct_body = struct_body(style, view_where_clause, &field_declares);
+        let field_inits = part
+            .members
+            .iter()
+            .map(|(member, src_member)| quote! { #member: &#mutability self.#src_member });
+
+        let accessor_ident = match self.mutable {
+            true => format_ident!("as_{}_mut", to_snake_case(part_ident)),
+            false => format_ident!("as_{}", to_snake_case(part_ident)),
+        };
+        let doc = match self.mutable {
+            true => format!("Mutably borrows the fields of `{part_ident}`."),
+            false => format!("Borrows the fields of `{part_ident}`."),
+        };
+
+        quote! {
+            #derive_attr
+            #(#forward_attrs)*
+            #vis struct #view_ident #view_generics #struct_body
+
+            impl #impl_generics #ident #ty_generics #where_clause {
+                #[doc = #doc]
+                #vis fn #accessor_ident<#lifetime>(&#lifetime #mutability self) -> #view_ident #view_ty_generics {
+                    #view_ident {
+                        #(#field_inits),*
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Wraps the field declarations into the body of a named or tuple struct, placing the where
+/// clause before the fields of a named struct and after the fields of a tuple struct.
+pub fn struct_body(
+    style: Style,
+    where_clause: Option<&WhereClause>,
+    field_declares: &[TokenStream],
+) -> TokenStream {
+    match style {
+        Style::Tuple => quote! { (#(#field_declares),*) #where_clause; },
+        _ => quote! { #where_clause { #(#field_declares),* } },
+    }
+}
+
+/// The identifiers and lifetimes referenced by a piece of syntax.
+#[derive(Debug, Default)]
+struct GenericsUsage {
+    idents: HashSet<Ident>,
+    lifetimes: HashSet<Ident>,
+}
+
+impl GenericsUsage {
+    fn of(visit: impl FnOnce(&mut Self)) -> Self {
+        let mut usage = Self::default();
+        visit(&mut usage);
+        usage
+    }
+
+    fn intersects(&self, other: &Self) -> bool {
+        !self.idents.is_disjoint(&other.idents) || !self.lifetimes.is_disjoint(&other.lifetimes)
+    }
+}
+
+impl<'ast> Visit<'ast> for GenericsUsage {
+    fn visit_path(&mut self, path: &'ast Path) {
+        // Type and const parameters can only appear as the first segment of a relative path,
+        // e.g. `T`, `T::Item` or `N`
+        if path.leading_colon.is_none() {
+            if let Some(segment) = path.segments.first() {
+                self.idents.insert(segment.ident.clone());
+            }
+        }
+        visit::visit_path(self, path);
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+        self.lifetimes.insert(lifetime.ident.clone());
+    }
+}
+
+/// Keeps only the generic parameters referenced by the given types.
+///
+/// Bounds and where predicates of the kept parameters are retained unless they mention a
+/// parameter that has been removed.
+pub fn used_generics<'a>(
+    generics: &Generics,
+    types: impl IntoIterator<Item = &'a Type>,
+) -> Generics {
+    let used = GenericsUsage::of(|usage| types.into_iter().for_each(|ty| usage.visit_type(ty)));
+
+    let mut unused = GenericsUsage::default();
+    generics.params.iter().for_each(|param| match param {
+        GenericParam::Type(param) if !used.idents.contains(&param.ident) => {
+            unused.idents.insert(param.ident.clone());
+        }
+        GenericParam::Const(param) if !used.idents.contains(&param.ident) => {
+            unused.idents.insert(param.ident.clone());
+        }
+        GenericParam::Lifetime(param) if !used.lifetimes.contains(&param.lifetime.ident) => {
+            unused.lifetimes.insert(param.lifetime.ident.clone());
+        }
+        _ => {}
+    });
+
+    let mentions_unused =
+        |visit: &dyn Fn(&mut GenericsUsage)| unused.intersects(&GenericsUsage::of(visit));
+
+    let params = generics
+        .params
+        .iter()
+        .filter(|param| match param {
+            GenericParam::Type(param) => !unused.idents.contains(&param.ident),
+            GenericParam::Const(param) => !unused.idents.contains(&param.ident),
+            GenericParam::Lifetime(param) => !unused.lifetimes.contains(&param.lifetime.ident),
+        })
+        .cloned()
+        .map(|mut param| {
+            match &mut param {
+                GenericParam::Type(param) => {
+                    param.bounds = param
+                        .bounds
+                        .iter()
+                        .filter(|bound| {
+                            !mentions_unused(&|usage| usage.visit_type_param_bound(bound))
+                        })
+                        .cloned()
+                        .collect();
+                    if param.bounds.is_empty() {
+                        param.colon_token = None;
+                    }
+                    if let Some(default) = &param.default {
+                        if mentions_unused(&|usage| usage.visit_type(default)) {
+                            param.eq_token = None;
+                            param.default = None;
+                        }
+                    }
+                }
+                GenericParam::Lifetime(param) => {
+                    param.bounds = param
+                        .bounds
+                        .iter()
+                        .filter(|bound| !mentions_unused(&|usage| usage.visit_lifetime(bound)))
+                        .cloned()
+                        .collect();
+                    if param.bounds.is_empty() {
+                        param.colon_token = None;
+                    }
+                }
+                GenericParam::Const(_) => {}
+            }
+            param
+        })
+        .collect::<Punctuated<_, _>>();
+
+    let where_clause = generics.where_clause.as_ref().and_then(|where_clause| {
+        let predicates = where_clause
+            .predicates
+            .iter()
+            .filter_map(|predicate| {
+                let mut predicate = predicate.clone();
+                match &mut predicate {
+                    WherePredicate::Type(predicate) => {
+                        if mentions_unused(&|usage| usage.visit_type(&predicate.bounded_ty)) {
+                            return None;
+                        }
+                        predicate.bounds = predicate
+                            .bounds
+                            .iter()
+                            .filter(|bound| {
+                                !mentions_unused(&|usage| usage.visit_type_param_bound(bound))
+                            })
+                            .cloned()
+                            .collect();
+                        (!predicate.bounds.is_empty()).then_some(())?;
+                    }
+                    WherePredicate::Lifetime(predicate) => {
+                        if unused.lifetimes.contains(&predicate.lifetime.ident) {
+                            return None;
+                        }
+                        predicate.bounds = predicate
+                            .bounds
+                            .iter()
+                            .filter(|bound| !mentions_unused(&|usage| usage.visit_lifetime(bound)))
+                            .cloned()
+                            .collect();
+                        (!predicate.bounds.is_empty()).then_some(())?;
+                    }
+                    _ => {}
+                }
+                Some(predicate)
+            })
+            .collect::<Punctuated<_, _>>();
+
+        (!predicates.is_empty()).then(|| WhereClause {
+            where_token: where_clause.where_token,
+            predicates,
+        })
+    });
+
+    Generics {
+        lt_token: generics.lt_token,
+        params,
+        gt_token: generics.gt_token,
+        where_clause,
+    }
+}
+
+/// Returns the generic parameters of `generics` which are not in `used`, the result of
+/// [`used_generics`], together with the bounds and where predicates `used` has left out.
+///
+/// Declared on a method in an impl of `used`, they make the method generic over the parameters of
+/// the original type which the generated type does not have.
+pub fn dropped_generics(generics: &Generics, used: &Generics) -> Generics {
+    let param_name = |param: &GenericParam| match param {
+        GenericParam::Type(param) => param.ident.to_string(),
+        GenericParam::Const(param) => param.ident.to_string(),
+        GenericParam::Lifetime(param) => param.lifetime.to_string(),
+    };
+    let used_params = used
+        .params
+        .iter()
+        .map(|param| (param_name(param), param))
+        .collect::<Vec<_>>();
+    let used_param = |param: &GenericParam| {
+        used_params
+            .iter()
+            .find(|(name, _)| *name == param_name(param))
+            .map(|(_, param)| *param)
+    };
+
+    let params = generics
+        .params
+        .iter()
+        .filter(|param| used_param(param).is_none())
+        .cloned()
+        .collect::<Punctuated<_, _>>();
+
+    // The bounds of the kept parameters which `used` has removed
+    let mut predicates = generics
+        .params
+        .iter()
+        .filter_map(|param| -> Option<WherePredicate> {
+            match (param, used_param(param)?) {
+                (GenericParam::Type(param), GenericParam::Type(used))
+                    if param.bounds.len() > used.bounds.len() =>
+                {
+                    let (ident, bounds) = (&param.ident, &param.bounds);
+                    Some(parse_quote! { #ident: #bounds })
+                }
+                (GenericParam::Lifetime(param), GenericParam::Lifetime(used))
+                    if param.bounds.len() > used.bounds.len() =>
+                {
+                    let (lifetime, bounds) = (&param.lifetime, &param.bounds);
+                    Some(parse_quote! { #lifetime: #bounds })
+                }
+                _ => None,
+            }
+        })
+        .collect::<Punctuated<_, _>>();
+    let used_predicates = used
+        .where_clause
+        .iter()
+        .flat_map(|where_clause| &where_clause.predicates)
+        .map(|predicate| predicate.to_token_stream().to_string())
+        .collect::<HashSet<_>>();
+    predicates.extend(
+        generics
+            .where_clause
+            .iter()
+            .flat_map(|where_clause| &where_clause.predicates)
+            .filter(|predicate| !used_predicates.contains(&predicate.to_token_stream().to_string()))
+            .cloned(),
+    );
+
+    Generics {
+        lt_token: generics.lt_token,
+        params,
+        gt_token: generics.gt_token,
+        where_clause: (!predicates.is_empty()).then(|| WhereClause {
+            where_token: Default::default(),
+            predicates,
+        }),
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq)]
 pub enum ForwardAttrsFilter {
     // forward all attributes
@@ -120,3 +852,122 @@ pub fn filter_forward_attrs<'a>(
         ForwardAttrsFilter::Not(not_allowed) => !not_allowed.contains(attr.path()),
     })
 }
+
+/// Returns the inner type `T` if `ty` is written as `Option<T>`.
+///
+/// Only the literal paths `Option`, `std::option::Option` and `core::option::Option` are
+/// recognized, as the macro cannot resolve type aliases.
+pub fn option_inner_type(ty: &Type) -> Option<&Type> {
+    wrapped_inner_type(
+        ty,
+        &["Option", "std::option::Option", "core::option::Option"],
+    )
+}
+
+/// Returns the type argument `T` if `ty` is `Wrapper<T>` where `Wrapper` is one of `wrappers`.
+///
+/// Wrappers are compared by the path as written, e.g. `std::option::Option`, without generic
+/// arguments.
+pub fn wrapped_inner_type<'a>(ty: &'a Type, wrappers: &[&str]) -> Option<&'a Type> {
+    let Type::Path(TypePath { path, .. }) = ty else {
+        return None;
+    };
+
+    let segments_str = &path_to_string(path);
+    let wrapper_segment = wrappers
+        .iter()
+        .find(|s| segments_str == *s)
+        .and_then(|_| path.segments.last());
+
+    wrapper_segment
+        .and_then(|path_seg| match &path_seg.arguments {
+            PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }) => {
+                args.first()
+            }
+            _ => None,
+        })
+        .and_then(|generic_arg| match generic_arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+}
+
+/// Returns the path as written without generic arguments, e.g. `std::option::Option`.
+pub fn path_to_string(path: &Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Generates an error struct listing the names of the fields that are missing in a fallible
+/// conversion, together with its `Display` and `Error` implementations.
+pub fn missing_fields_error(vis: &Visibility, error_ident: &Ident, source: &Ident) -> TokenStream {
+    let doc = format!("Error returned when some required fields of `{source}` are missing.");
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #vis struct #error_ident {
+            /// The names of the missing fields.
+            pub missing_fields: Vec<&'static str>,
+        }
+
+        impl core::fmt::Display for #error_ident {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "missing fields: {}", self.missing_fields.join(", "))
+            }
+        }
+
+        impl std::error::Error for #error_ident {}
+    }
+}
+
+/// Generates the body of a fallible conversion into `target`, which collects the names of all
+/// missing fields.
+///
+/// Each field is given by its member in the constructed struct, the expression of its value, and
+/// whether the value is an `Option` which is missing when `None`.
+pub fn try_from_body(
+    error_ident: &Ident,
+    target: TokenStream,
+    fields: impl IntoIterator<Item = (Member, TokenStream, bool)>,
+) -> TokenStream {
+    let mut field_try_from = Vec::new();
+    let mut field_inits = Vec::new();
+    let mut checked_fields = Vec::new();
+
+    for (member, value, checked) in fields {
+        let local = format_ident!("__{}", member_name(&member));
+        field_inits.push(quote! { #member: #local });
+        if checked {
+            let field_name = member_name(&member);
+            field_try_from.push(quote! {
+                let #local = #value;
+                if #local.is_none() {
+                    missing_fields.push(#field_name);
+                }
+            });
+            checked_fields.push(local);
+        } else {
+            field_try_from.push(quote! { let #local = #value; });
+        }
+    }
+
+    if checked_fields.is_empty() {
+        quote! {
+            #(#field_try_from)*
+            Ok(#target { #(#field_inits),* })
+        }
+    } else {
+        quote! {
+            let mut missing_fields = Vec::new();
+            #(#field_try_from)*
+            match (#(#checked_fields,)*) {
+                (#(Some(#checked_fields),)*) => Ok(#target { #(#field_inits),* }),
+                _ => Err(#error_ident { missing_fields }),
+            }
+        }
+    }
+}
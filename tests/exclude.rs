@@ -11,6 +11,10 @@ fn exclude() {
     t.compile_fail("tests/exclude/05-empty-ident.rs");
     t.compile_fail("tests/exclude/06-no-variants.rs");
     t.compile_fail("tests/exclude/07-empty-variants.rs");
-    t.pass("tests/exclude/08-variant-not-exist.rs");
+    t.compile_fail("tests/exclude/08-variant-not-exist.rs");
     t.pass("tests/exclude/09-forward-attrs.rs");
+    t.pass("tests/exclude/10-generics.rs");
+    t.pass("tests/exclude/11-unused-generics.rs");
+    t.pass("tests/exclude/12-lenient.rs");
+    t.compile_fail("tests/exclude/13-ident-collision.rs");
 }
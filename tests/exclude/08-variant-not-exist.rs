@@ -1,11 +1,14 @@
 use utility_types::Exclude;
 
 #[derive(Debug, PartialEq, Exclude)]
-#[exclude(arg(
-    ident = Terrestrial,
-    variants(Foo, Bar),
-    derive(Debug, PartialEq, Clone, Copy)
-))]
+#[exclude(
+    arg(
+        ident = Terrestrial,
+        variants(Foo, Bar),
+        derive(Debug, PartialEq, Clone, Copy)
+    ),
+    arg(ident = Inner, variants(Mercury, Venus, Erath, Venus))
+)]
 pub enum Planet {
     Mercury,
     Venus,
@@ -0,0 +1,22 @@
+use std::fmt::Debug;
+
+use utility_types::Exclude;
+
+#[derive(Debug, PartialEq, Exclude)]
+#[exclude(arg(ident = ExcludeAB, variants(C), derive(Debug, PartialEq)))]
+pub enum E<'a, T: Clone, U, const N: usize>
+where
+    U: Debug,
+{
+    A(&'a T),
+    B { b: [U; N] },
+    C,
+}
+
+fn main() {
+    let e: E<'_, i32, i32, 2> = ExcludeAB::B { b: [1, 2] }.into();
+    assert_eq!(e, E::B { b: [1, 2] });
+
+    let e: E<'_, i32, i32, 2> = ExcludeAB::A(&1).into();
+    assert_eq!(e, E::A(&1));
+}
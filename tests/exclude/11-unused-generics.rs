@@ -0,0 +1,25 @@
+use std::fmt::Debug;
+
+use utility_types::Exclude;
+
+#[derive(Debug, PartialEq, Exclude)]
+#[exclude(
+    arg(ident = ExcludeB, variants(B), derive(Debug, PartialEq)),
+    arg(ident = ExcludeAB, variants(A, B), derive(Debug, PartialEq))
+)]
+pub enum E<'a, T: Clone, U, const N: usize>
+where
+    U: Debug,
+{
+    A(&'a T),
+    B { b: [U; N] },
+    C,
+}
+
+fn main() {
+    let e: E<'_, i32, i32, 2> = ExcludeB::A(&1).into();
+    assert_eq!(e, E::A(&1));
+
+    let e: E<'_, i32, i32, 2> = ExcludeAB::C.into();
+    assert_eq!(e, E::C);
+}
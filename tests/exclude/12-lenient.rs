@@ -0,0 +1,11 @@
+use utility_types::Exclude;
+
+#[derive(Debug, PartialEq, Exclude)]
+#[exclude(arg(ident = Inner, variants(Mercury, Venus, Foo), derive(Debug, PartialEq), lenient))]
+pub enum Planet {
+    Mercury,
+    Venus,
+    Earth,
+}
+
+fn main() {}
@@ -11,6 +11,11 @@ fn extract() {
     t.compile_fail("tests/extract/05-empty-ident.rs");
     t.compile_fail("tests/extract/06-no-variants.rs");
     t.compile_fail("tests/extract/07-empty-variants.rs");
-    t.pass("tests/extract/08-variant-not-exist.rs");
+    t.compile_fail("tests/extract/08-variant-not-exist.rs");
     t.pass("tests/extract/09-forward-attrs.rs");
+    t.pass("tests/extract/10-generics.rs");
+    t.pass("tests/extract/11-unused-generics.rs");
+    t.pass("tests/extract/12-lenient.rs");
+    t.compile_fail("tests/extract/13-multiple-errors.rs");
+    t.compile_fail("tests/extract/14-ident-collision.rs");
 }
@@ -1,11 +1,14 @@
 use utility_types::Extract;
 
 #[derive(Debug, PartialEq, Extract)]
-#[extract(arg(
-    ident = Terrestrial,
-    variants(Foo, Bar),
-    derive(Debug, PartialEq, Clone, Copy)
-))]
+#[extract(
+    arg(
+        ident = Terrestrial,
+        variants(Foo, Bar),
+        derive(Debug, PartialEq, Clone, Copy)
+    ),
+    arg(ident = Inner, variants(Mercury, Venus, Erath, Venus))
+)]
 pub enum Planet {
     Mercury,
     Venus,
@@ -0,0 +1,25 @@
+use std::fmt::Debug;
+
+use utility_types::Extract;
+
+#[derive(Debug, PartialEq, Extract)]
+#[extract(
+    arg(ident = ExtractA, variants(A), derive(Debug, PartialEq)),
+    arg(ident = ExtractC, variants(C), derive(Debug, PartialEq))
+)]
+pub enum E<'a, T: Clone, U, const N: usize>
+where
+    U: Debug,
+{
+    A(&'a T),
+    B { b: [U; N] },
+    C,
+}
+
+fn main() {
+    let e: E<'_, i32, i32, 2> = ExtractA::A(&1).into();
+    assert_eq!(e, E::A(&1));
+
+    let e: E<'_, i32, i32, 2> = ExtractC::C.into();
+    assert_eq!(e, E::C);
+}
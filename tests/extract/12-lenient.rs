@@ -0,0 +1,11 @@
+use utility_types::Extract;
+
+#[derive(Debug, PartialEq, Extract)]
+#[extract(arg(ident = Inner, variants(Mercury, Venus, Foo), derive(Debug, PartialEq), lenient))]
+pub enum Planet {
+    Mercury,
+    Venus,
+    Earth,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use utility_types::Extract;
+
+#[derive(Debug, PartialEq, Extract)]
+#[extract(
+    arg(variants(Mercury)),
+    arg(ident = Outer, variants(Jupiter, "Saturn")),
+    arg(ident = Inner)
+)]
+pub enum Planet {
+    Mercury,
+    Venus,
+    Jupiter,
+    Saturn,
+}
+
+fn main() {}
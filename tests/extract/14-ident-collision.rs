@@ -0,0 +1,17 @@
+use utility_types::Extract;
+
+#[derive(Debug, PartialEq, Extract)]
+#[extract(
+    arg(ident = Inner, variants(Mercury, Venus)),
+    arg(ident = Inner, variants(Mercury)),
+    arg(ident = Planet, variants(Jupiter)),
+    arg(ident = Outer, variants(Jupiter, Satrun))
+)]
+pub enum Planet {
+    Mercury,
+    Venus,
+    Jupiter,
+    Saturn,
+}
+
+fn main() {}
@@ -11,6 +11,13 @@ fn omit() {
     t.compile_fail("tests/omit/05-no-fields.rs");
     t.compile_fail("tests/omit/06-empty-fields.rs");
     t.compile_fail("tests/omit/07-field-not-ident.rs");
-    t.pass("tests/omit/08-field-not-exist.rs");
+    t.compile_fail("tests/omit/08-field-not-exist.rs");
     t.pass("tests/omit/09-forward-attrs.rs");
+    t.pass("tests/omit/10-tuple-struct.rs");
+    t.pass("tests/omit/11-generics.rs");
+    t.pass("tests/omit/12-unused-generics.rs");
+    t.pass("tests/omit/13-lenient.rs");
+    t.compile_fail("tests/omit/14-ident-collision.rs");
+    t.pass("tests/omit/15-split.rs");
+    t.pass("tests/omit/16-views.rs");
 }
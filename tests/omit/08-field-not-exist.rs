@@ -1,7 +1,10 @@
 use utility_types::Omit;
 
 #[derive(Omit, Debug, Clone, PartialEq)]
-#[omit(arg(ident = Meta, fields(foo), derive(Debug, PartialEq)))]
+#[omit(
+    arg(ident = Meta, fields(foo), derive(Debug, PartialEq)),
+    arg(ident = Header, fields(titel, author, author), derive(Debug, PartialEq))
+)]
 struct Article {
     title: String,
     author: String,
@@ -0,0 +1,33 @@
+use std::fmt::Debug;
+
+use utility_types::Omit;
+
+#[derive(Clone, Omit)]
+#[omit(arg(ident = OmitC, fields(c), derive(Debug, PartialEq)))]
+pub struct A<'a, T: Clone, U, const N: usize>
+where
+    U: Debug,
+{
+    a: &'a T,
+    b: [U; N],
+    c: usize,
+}
+
+#[derive(Clone, Omit)]
+#[omit(arg(ident = OmitB, fields(2), derive(Debug, PartialEq)))]
+pub struct B<T>(T, Option<T>, usize)
+where
+    T: Debug;
+
+fn main() {
+    let a = A {
+        a: &1,
+        b: [2, 3],
+        c: 4,
+    };
+    let omit: OmitC<'_, i32, i32, 2> = a.into();
+    assert_eq!(omit, OmitC { a: &1, b: [2, 3] });
+
+    let omit: OmitB<i32> = B(1, None, 2).into();
+    assert_eq!(omit, OmitB(1, None));
+}
@@ -0,0 +1,49 @@
+use std::fmt::Debug;
+
+use utility_types::Omit;
+
+#[derive(Clone, Omit)]
+#[omit(
+    arg(ident = OmitBC, fields(b, c), derive(Debug, PartialEq)),
+    arg(ident = OmitA, fields(a, d), derive(Debug, PartialEq))
+)]
+pub struct A<'a, T: Clone, U, const N: usize>
+where
+    U: Debug,
+{
+    a: &'a str,
+    b: T,
+    c: [U; N],
+    d: String,
+}
+
+#[derive(Clone, Omit)]
+#[omit(arg(ident = OmitFirst, fields(0), derive(Debug, PartialEq)))]
+pub struct B<T, U>(T, U)
+where
+    T: Debug,
+    U: Debug;
+
+fn main() {
+    let a = A::<'_, i64, i32, 2> {
+        a: "a",
+        b: 1,
+        c: [2, 3],
+        d: "d".to_string(),
+    };
+
+    let omit: OmitBC<'_> = a.clone().into();
+    assert_eq!(
+        omit,
+        OmitBC {
+            a: "a",
+            d: "d".to_string()
+        }
+    );
+
+    let omit: OmitA<i64, i32, 2> = a.into();
+    assert_eq!(omit, OmitA { b: 1, c: [2, 3] });
+
+    let omit: OmitFirst<&str> = B(1, "second").into();
+    assert_eq!(omit, OmitFirst("second"));
+}
@@ -0,0 +1,17 @@
+use utility_types::Omit;
+
+#[derive(Omit, Debug, Clone, PartialEq)]
+#[omit(
+    arg(ident = Meta, fields(title, author)),
+    arg(ident = Meta, fields(content)),
+    arg(ident = Article, fields(tags)),
+    arg(ident = Body, fields(contnet))
+)]
+struct Article {
+    title: String,
+    author: String,
+    content: String,
+    tags: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,42 @@
+use utility_types::Omit;
+
+#[derive(Debug, Clone, PartialEq, Omit)]
+#[omit(
+    arg(ident = Summary, fields(content), derive(Debug, PartialEq), split(ident = Content)),
+    forward_attrs()
+)]
+pub struct Article {
+    title: String,
+    content: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Omit)]
+#[omit(arg(ident = OmitFirst, fields(0), derive(Debug, PartialEq), split), forward_attrs())]
+pub struct Pair(u8, bool);
+
+fn main() {
+    let article = Article {
+        title: "title".to_string(),
+        content: "content".to_string(),
+    };
+
+    let (summary, content) = article.clone().split_summary();
+    assert_eq!(
+        summary,
+        Summary {
+            title: "title".to_string()
+        }
+    );
+    assert_eq!(
+        content,
+        Content {
+            content: "content".to_string()
+        }
+    );
+    assert_eq!(Article::from((summary, content)), article);
+
+    let (second, first) = Pair(1, true).split_omit_first();
+    assert_eq!(second, OmitFirst(true));
+    assert_eq!(first, OmitFirstRest(1));
+    assert_eq!(Pair::from((second, first)), Pair(1, true));
+}
@@ -0,0 +1,40 @@
+use utility_types::Omit;
+
+#[derive(Debug, PartialEq, Omit)]
+#[omit(
+    arg(ident = Public, fields(secret), ref_view(derive(Debug, PartialEq)), mut_view(ident = PublicEdit)),
+    forward_attrs()
+)]
+pub struct Account<T> {
+    name: String,
+    secret: String,
+    data: T,
+}
+
+fn main() {
+    let mut account = Account {
+        name: "name".to_string(),
+        secret: "secret".to_string(),
+        data: 1,
+    };
+
+    assert_eq!(
+        account.as_public(),
+        PublicRef {
+            name: &"name".to_string(),
+            data: &1,
+        }
+    );
+
+    let PublicEdit { name, data } = account.as_public_mut();
+    name.push('!');
+    *data += 1;
+    assert_eq!(
+        account,
+        Account {
+            name: "name!".to_string(),
+            secret: "secret".to_string(),
+            data: 2,
+        }
+    );
+}
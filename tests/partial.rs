@@ -11,4 +11,29 @@ fn partial() {
     t.pass("tests/partial/05-ident-str.rs");
     t.pass("tests/partial/06-derive-empty.rs");
     t.pass("tests/partial/07-forward-attrs.rs");
+    t.pass("tests/partial/08-flatten-option.rs");
+    t.pass("tests/partial/09-apply-to.rs");
+    t.pass("tests/partial/10-nested.rs");
+    t.compile_fail("tests/partial/11-nested-flatten.rs");
+    t.pass("tests/partial/12-try-from.rs");
+    t.pass("tests/partial/13-diff.rs");
+    t.pass("tests/partial/14-tuple-struct.rs");
+    t.pass("tests/partial/15-merge-patch.rs");
+    t.pass("tests/partial/16-skip.rs");
+    t.pass("tests/partial/17-builder.rs");
+    t.pass("tests/partial/18-typestate-builder.rs");
+    t.compile_fail("tests/partial/19-typestate-builder-missing.rs");
+    t.pass("tests/partial/20-merge.rs");
+    t.pass("tests/partial/21-introspection.rs");
+    t.pass("tests/partial/22-multi-args.rs");
+    t.pass("tests/partial/23-selective.rs");
+    t.compile_fail("tests/partial/24-selective-unknown.rs");
+    t.pass("tests/partial/25-generics.rs");
+    t.compile_fail("tests/partial/26-ident-collision.rs");
+    t.compile_fail("tests/partial/27-builder-duplicate.rs");
+    t.compile_fail("tests/partial/28-derived-ident-collision.rs");
+    t.compile_fail("tests/partial/29-container-options.rs");
+    t.pass("tests/partial/30-raw-ident.rs");
+    t.compile_fail("tests/partial/31-builder-keep-without-default.rs");
+    t.compile_fail("tests/partial/32-diff-flatten-option.rs");
 }
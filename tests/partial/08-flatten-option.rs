@@ -0,0 +1,63 @@
+use utility_types::Partial;
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialA, derive(Debug, PartialEq), flatten_option)]
+pub struct A {
+    a: usize,
+    b: Option<usize>,
+    #[partial(default = Some(3))]
+    c: Option<usize>,
+    #[partial(flatten_option = false)]
+    d: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialB, derive(Debug, PartialEq))]
+pub struct B {
+    a: Option<usize>,
+    #[partial(flatten_option)]
+    b: Option<usize>,
+}
+
+fn main() {
+    let a = A {
+        a: 0,
+        b: Some(1),
+        c: Some(2),
+        d: None,
+    };
+
+    let pa: PartialA = a.into();
+
+    assert_eq!(
+        pa,
+        PartialA {
+            a: Some(0),
+            b: Some(1),
+            c: Some(2),
+            d: Some(None),
+        }
+    );
+
+    let a: A = PartialA {
+        a: None,
+        b: None,
+        c: None,
+        d: None,
+    }
+    .into();
+
+    assert_eq!(
+        a,
+        A {
+            a: 0,
+            b: None,
+            c: Some(3),
+            d: None,
+        }
+    );
+
+    let pb: PartialB = B { a: None, b: None }.into();
+
+    assert_eq!(pb, PartialB { a: Some(None), b: None });
+}
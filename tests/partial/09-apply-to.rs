@@ -0,0 +1,56 @@
+use utility_types::Partial;
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialA, derive(Debug, PartialEq))]
+pub struct A {
+    a: usize,
+    b: Option<usize>,
+    #[partial(flatten_option)]
+    c: Option<usize>,
+    d: String,
+}
+
+fn main() {
+    let mut a = A {
+        a: 0,
+        b: Some(1),
+        c: Some(2),
+        d: "hello".to_string(),
+    };
+
+    PartialA {
+        a: Some(10),
+        b: Some(None),
+        c: None,
+        d: None,
+    }
+    .apply_to(&mut a);
+
+    assert_eq!(
+        a,
+        A {
+            a: 10,
+            b: None,
+            c: Some(2),
+            d: "hello".to_string(),
+        }
+    );
+
+    PartialA {
+        a: None,
+        b: None,
+        c: Some(20),
+        d: Some("world".to_string()),
+    }
+    .apply_to(&mut a);
+
+    assert_eq!(
+        a,
+        A {
+            a: 10,
+            b: None,
+            c: Some(20),
+            d: "world".to_string(),
+        }
+    );
+}
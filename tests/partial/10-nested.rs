@@ -0,0 +1,87 @@
+use utility_types::Partial;
+
+#[derive(Debug, Default, PartialEq, Partial)]
+#[partial(ident = PartialAddress, derive(Debug, Default, PartialEq))]
+pub struct Address {
+    city: String,
+    street: String,
+}
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialUser, derive(Debug, PartialEq))]
+pub struct User {
+    name: String,
+    #[partial(nested = PartialAddress)]
+    address: Address,
+}
+
+fn main() {
+    let user = User {
+        name: "Alice".to_string(),
+        address: Address {
+            city: "Paris".to_string(),
+            street: "Rue de Rivoli".to_string(),
+        },
+    };
+
+    let mut user_clone = User {
+        name: "Alice".to_string(),
+        address: Address {
+            city: "Paris".to_string(),
+            street: "Rue de Rivoli".to_string(),
+        },
+    };
+
+    let partial: PartialUser = user.into();
+
+    assert_eq!(
+        partial,
+        PartialUser {
+            name: Some("Alice".to_string()),
+            address: Some(PartialAddress {
+                city: Some("Paris".to_string()),
+                street: Some("Rue de Rivoli".to_string()),
+            }),
+        }
+    );
+
+    PartialUser {
+        name: None,
+        address: Some(PartialAddress {
+            city: Some("Lyon".to_string()),
+            street: None,
+        }),
+    }
+    .apply_to(&mut user_clone);
+
+    assert_eq!(
+        user_clone,
+        User {
+            name: "Alice".to_string(),
+            address: Address {
+                city: "Lyon".to_string(),
+                street: "Rue de Rivoli".to_string(),
+            },
+        }
+    );
+
+    let user: User = PartialUser {
+        name: None,
+        address: Some(PartialAddress {
+            city: Some("Lyon".to_string()),
+            street: None,
+        }),
+    }
+    .into();
+
+    assert_eq!(
+        user,
+        User {
+            name: String::new(),
+            address: Address {
+                city: "Lyon".to_string(),
+                street: String::new(),
+            },
+        }
+    );
+}
@@ -0,0 +1,16 @@
+use utility_types::Partial;
+
+#[derive(Default, Partial)]
+#[partial(ident = PartialAddress)]
+pub struct Address {
+    city: String,
+}
+
+#[derive(Partial)]
+#[partial(ident = PartialUser)]
+pub struct User {
+    #[partial(nested = PartialAddress, flatten_option)]
+    address: Address,
+}
+
+fn main() {}
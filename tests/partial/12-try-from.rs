@@ -0,0 +1,63 @@
+use utility_types::Partial;
+
+pub struct NoDefault(usize);
+
+#[derive(Partial)]
+#[partial(ident = PartialAddress, try_from)]
+pub struct Address {
+    city: String,
+}
+
+#[derive(Partial)]
+#[partial(ident = PartialUser, try_from)]
+pub struct User {
+    id: NoDefault,
+    name: String,
+    #[partial(default = 18)]
+    age: u8,
+    #[partial(flatten_option)]
+    email: Option<String>,
+    #[partial(nested = PartialAddress)]
+    address: Address,
+}
+
+fn main() {
+    let user = User::try_from(PartialUser {
+        id: Some(NoDefault(1)),
+        name: Some("Alice".to_string()),
+        age: None,
+        email: None,
+        address: Some(PartialAddress {
+            city: Some("Paris".to_string()),
+        }),
+    })
+    .unwrap();
+
+    assert_eq!(user.id.0, 1);
+    assert_eq!(user.name, "Alice");
+    assert_eq!(user.age, 18);
+    assert_eq!(user.email, None);
+    assert_eq!(user.address.city, "Paris");
+
+    let err = User::try_from(PartialUser {
+        id: None,
+        name: Some("Alice".to_string()),
+        age: Some(20),
+        email: None,
+        address: Some(PartialAddress { city: None }),
+    })
+    .err()
+    .unwrap();
+
+    assert_eq!(
+        err,
+        PartialUserError {
+            missing_fields: vec!["id", "address"]
+        }
+    );
+    assert_eq!(err.to_string(), "missing fields: id, address");
+
+    let err = Address::try_from(PartialAddress { city: None }).err().unwrap();
+
+    assert_eq!(err.missing_fields, vec!["city"]);
+}
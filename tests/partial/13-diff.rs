@@ -0,0 +1,114 @@
+use utility_types::Partial;
+
+#[derive(Clone, Debug, Default, PartialEq, Partial)]
+#[partial(ident = PartialAddress, derive(Debug, PartialEq), diff)]
+pub struct Address {
+    city: String,
+    street: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Partial)]
+#[partial(ident = PartialUser, derive(Debug, PartialEq), diff)]
+pub struct User {
+    name: String,
+    age: u8,
+    email: Option<String>,
+    #[partial(nested = PartialAddress)]
+    address: Address,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Partial)]
+#[partial(ident = PartialTagged, derive(Debug, PartialEq), diff)]
+pub struct Tagged<T: Clone + Default + PartialEq> {
+    tag: T,
+}
+
+#[derive(Clone, Debug, PartialEq, Partial)]
+#[partial(ident = PartialPost, derive(Debug, PartialEq), diff)]
+pub struct Post<T: Clone + Default + PartialEq> {
+    #[partial(nested = "PartialTagged<T>")]
+    tagged: Tagged<T>,
+}
+
+#[derive(Clone, Debug, PartialEq, Partial)]
+#[partial(ident = PartialContact, derive(Debug, PartialEq), diff, merge_patch)]
+pub struct Contact {
+    email: Option<String>,
+}
+
+fn main() {
+    let old = User {
+        name: "Alice".to_string(),
+        age: 18,
+        email: None,
+        address: Address {
+            city: "Paris".to_string(),
+            street: "Rue de Rivoli".to_string(),
+        },
+    };
+
+    let new = User {
+        name: "Alice".to_string(),
+        age: 19,
+        email: Some("alice@example.com".to_string()),
+        address: Address {
+            city: "Lyon".to_string(),
+            street: "Rue de Rivoli".to_string(),
+        },
+    };
+
+    let diff = PartialUser::diff(&old, &new);
+
+    assert_eq!(
+        diff,
+        PartialUser {
+            name: None,
+            age: Some(19),
+            email: Some(Some("alice@example.com".to_string())),
+            address: Some(PartialAddress {
+                city: Some("Lyon".to_string()),
+                street: None,
+            }),
+        }
+    );
+
+    let mut patched = old.clone();
+    diff.apply_to(&mut patched);
+
+    assert_eq!(patched, new);
+
+    assert_eq!(
+        PartialUser::diff(&old, &old),
+        PartialUser {
+            name: None,
+            age: None,
+            email: None,
+            address: None,
+        }
+    );
+
+    assert_eq!(
+        PartialPost::diff(&Post { tagged: Tagged { tag: 1 } }, &Post { tagged: Tagged { tag: 2 } }),
+        PartialPost {
+            tagged: Some(PartialTagged { tag: Some(2) }),
+        }
+    );
+
+    let old = Contact {
+        email: Some("alice@example.com".to_string()),
+    };
+    let new = Contact { email: None };
+    let diff = PartialContact::diff(&old, &new);
+
+    assert_eq!(
+        diff,
+        PartialContact {
+            email: PartialContactPatch::Clear,
+        }
+    );
+
+    let mut patched = old.clone();
+    diff.apply_to(&mut patched);
+
+    assert_eq!(patched, new);
+}
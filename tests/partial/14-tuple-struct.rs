@@ -0,0 +1,23 @@
+use utility_types::Partial;
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialA, derive(Debug, PartialEq), try_from)]
+pub struct A(usize, #[partial(flatten_option)] Option<usize>, String);
+
+fn main() {
+    let a = A(0, Some(1), "hello".to_string());
+
+    let pa: PartialA = a.into();
+
+    assert_eq!(pa, PartialA(Some(0), Some(1), Some("hello".to_string())));
+
+    let mut a = A::try_from(pa).unwrap();
+
+    PartialA(Some(10), None, None).apply_to(&mut a);
+
+    assert_eq!(a, A(10, Some(1), "hello".to_string()));
+
+    let err = A::try_from(PartialA(None, None, None)).err().unwrap();
+
+    assert_eq!(err.missing_fields, vec!["0", "2"]);
+}
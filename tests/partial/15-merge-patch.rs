@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use utility_types::Partial;
+
+#[derive(Clone, Debug, PartialEq, Partial)]
+#[partial(
+    ident = PartialUser,
+    derive(Debug, PartialEq, Serialize, Deserialize),
+    merge_patch(serde),
+    diff
+)]
+pub struct User {
+    name: String,
+    email: Option<String>,
+    phone: Option<String>,
+}
+
+fn main() {
+    let patch: PartialUser = serde_json::from_str(r#"{"email":null,"phone":"123"}"#).unwrap();
+
+    assert_eq!(
+        patch,
+        PartialUser {
+            name: None,
+            email: PartialUserPatch::Clear,
+            phone: PartialUserPatch::Set("123".to_string()),
+        }
+    );
+
+    let mut user = User {
+        name: "Alice".to_string(),
+        email: Some("alice@example.com".to_string()),
+        phone: None,
+    };
+    let old = user.clone();
+
+    patch.apply_to(&mut user);
+
+    assert_eq!(
+        user,
+        User {
+            name: "Alice".to_string(),
+            email: None,
+            phone: Some("123".to_string()),
+        }
+    );
+
+    let patch: PartialUser = serde_json::from_str(r#"{"name":"Bob"}"#).unwrap();
+
+    assert_eq!(
+        patch,
+        PartialUser {
+            name: Some("Bob".to_string()),
+            email: PartialUserPatch::Unchanged,
+            phone: PartialUserPatch::Unchanged,
+        }
+    );
+
+    let diff = PartialUser::diff(&old, &user);
+
+    assert_eq!(
+        serde_json::to_string(&diff).unwrap(),
+        r#"{"email":null,"phone":"123"}"#
+    );
+}
@@ -0,0 +1,69 @@
+use utility_types::Partial;
+
+#[derive(Clone, Debug, PartialEq, Partial)]
+#[partial(ident = PartialUser, derive(Debug, PartialEq), diff)]
+pub struct User {
+    #[partial(skip)]
+    id: u64,
+    name: String,
+    #[partial(skip, default = 42)]
+    created_at: u64,
+}
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialPoint, derive(Debug, PartialEq), try_from)]
+pub struct Point(#[partial(skip)] u8, u8, u8);
+
+fn main() {
+    let mut user = User {
+        id: 1,
+        name: "Alice".to_string(),
+        created_at: 100,
+    };
+
+    let partial: PartialUser = user.clone().into();
+
+    assert_eq!(
+        partial,
+        PartialUser {
+            name: Some("Alice".to_string()),
+        }
+    );
+
+    PartialUser {
+        name: Some("Bob".to_string()),
+    }
+    .apply_to(&mut user);
+
+    assert_eq!(
+        user,
+        User {
+            id: 1,
+            name: "Bob".to_string(),
+            created_at: 100,
+        }
+    );
+
+    let user: User = partial.into();
+
+    assert_eq!(
+        user,
+        User {
+            id: 0,
+            name: "Alice".to_string(),
+            created_at: 42,
+        }
+    );
+
+    let partial: PartialPoint = Point(1, 2, 3).into();
+
+    assert_eq!(partial, PartialPoint(Some(2), Some(3)));
+    assert_eq!(Point::try_from(partial).unwrap(), Point(0, 2, 3));
+    assert_eq!(
+        Point::try_from(PartialPoint(Some(2), None))
+            .err()
+            .unwrap()
+            .missing_fields,
+        vec!["2"]
+    );
+}
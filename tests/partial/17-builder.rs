@@ -0,0 +1,146 @@
+use utility_types::Partial;
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialUser, builder)]
+pub struct User {
+    name: String,
+    #[partial(default = 18)]
+    age: u8,
+    #[partial(nested = PartialAddress)]
+    address: Address,
+}
+
+#[derive(Debug, Default, PartialEq, Partial)]
+#[partial(ident = PartialAddress)]
+pub struct Address {
+    city: String,
+}
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialCity, builder, try_from)]
+pub struct City {
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialQueue, builder(ident = QueueConfig))]
+pub struct Queue {
+    len: usize,
+    merge: bool,
+}
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialNick, builder, try_from, fields(nick))]
+pub struct Nick {
+    name: String,
+    nick: Option<String>,
+    #[partial(keep, default = 1)]
+    rank: u8,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Id(u32);
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialJob, builder, fields(name))]
+pub struct Job {
+    name: String,
+    #[partial(default = Id(7))]
+    id: Id,
+}
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialTask, builder, try_from, fields(name))]
+pub struct Task {
+    name: String,
+    id: Id,
+}
+
+fn main() {
+    let user = User::builder()
+        .name("Alice".to_string())
+        .address(Address {
+            city: "Paris".to_string(),
+        })
+        .build();
+
+    assert_eq!(
+        user,
+        User {
+            name: "Alice".to_string(),
+            age: 18,
+            address: Address {
+                city: "Paris".to_string(),
+            },
+        }
+    );
+
+    let city = City::builder().name("Lyon".to_string()).build().unwrap();
+
+    assert_eq!(
+        city,
+        City {
+            name: "Lyon".to_string(),
+        }
+    );
+
+    let err = City::builder().build().err().unwrap();
+
+    assert_eq!(err.missing_fields, vec!["name"]);
+
+    let queue: Queue = QueueConfig::build(Queue::builder().len(8).merge(true));
+
+    assert_eq!(
+        queue,
+        Queue {
+            len: 8,
+            merge: true,
+        }
+    );
+
+    let partial = PartialQueue::from(Queue::builder().len(2));
+
+    assert_eq!(partial.len, Some(2));
+
+    let err = Nick::builder().nick(None).build().err().unwrap();
+
+    assert_eq!(err.missing_fields, vec!["name"]);
+
+    let nick = Nick::builder().name("Bob".to_string()).nick(None).build();
+
+    assert_eq!(
+        nick,
+        Ok(Nick {
+            name: "Bob".to_string(),
+            nick: None,
+            rank: 1,
+        })
+    );
+
+    let job = Job::builder().name("build".to_string()).build();
+
+    assert_eq!(
+        job,
+        Job {
+            name: "build".to_string(),
+            id: Id(7),
+        }
+    );
+
+    let task = Task::builder()
+        .name("test".to_string())
+        .id(Id(1))
+        .build();
+
+    assert_eq!(
+        task,
+        Ok(Task {
+            name: "test".to_string(),
+            id: Id(1),
+        })
+    );
+
+    let err = Task::builder().name("test".to_string()).build().err().unwrap();
+
+    assert_eq!(err.missing_fields, vec!["id"]);
+}
@@ -0,0 +1,56 @@
+#![allow(non_snake_case)]
+
+use utility_types::Partial;
+
+pub struct NoDefault(u64);
+
+#[derive(Partial)]
+#[partial(ident = PartialUser, builder(typestate))]
+pub struct User {
+    #[partial(skip, default = NoDefault(7))]
+    id: NoDefault,
+    name: String,
+    #[partial(default = 18)]
+    age: u8,
+    #[partial(flatten_option)]
+    email: Option<String>,
+    extra: u8,
+}
+
+#[derive(Partial)]
+#[partial(ident = PartialPair, builder(typestate))]
+pub struct Pair {
+    a: u8,
+    _a: u8,
+    a_b: u8,
+    a__b: u8,
+}
+
+fn main() {
+    let user = User::builder()
+        .extra(1u8)
+        .age(20)
+        .name("Alice".to_string())
+        .build();
+
+    assert_eq!(user.id.0, 7);
+    assert_eq!(user.name, "Alice");
+    assert_eq!(user.age, 20);
+    assert_eq!(user.email, None);
+    assert_eq!(user.extra, 1);
+
+    let user = User::builder()
+        .name("Bob".to_string())
+        .email(Some("bob@example.com".to_string()))
+        .extra(2)
+        .build();
+
+    assert_eq!(user.name, "Bob");
+    assert_eq!(user.age, 18);
+    assert_eq!(user.email.as_deref(), Some("bob@example.com"));
+    assert_eq!(user.extra, 2);
+
+    let pair = Pair::builder().a(1).a__b(4)._a(2).a_b(3).build();
+
+    assert_eq!((pair.a, pair._a, pair.a_b, pair.a__b), (1, 2, 3, 4));
+}
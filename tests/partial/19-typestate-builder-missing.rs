@@ -0,0 +1,12 @@
+use utility_types::Partial;
+
+#[derive(Partial)]
+#[partial(ident = PartialUser, builder(typestate, ident = UserBuilder))]
+pub struct User {
+    name: String,
+    age: u8,
+}
+
+fn main() {
+    let _user = User::builder().name("Alice".to_string()).build();
+}
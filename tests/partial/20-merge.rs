@@ -0,0 +1,82 @@
+use utility_types::Partial;
+
+pub trait Merge {
+    fn merge(self, higher: Self) -> Self;
+}
+
+fn layer<T: Merge>(layers: Vec<T>) -> Option<T> {
+    layers.into_iter().reduce(Merge::merge)
+}
+
+#[derive(Debug, Default, PartialEq, Partial)]
+#[partial(ident = PartialLog, derive(Debug, PartialEq), merge_trait = Merge)]
+pub struct Log {
+    level: String,
+    file: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialConfig, derive(Debug, PartialEq), merge_trait = Merge)]
+pub struct Config {
+    host: String,
+    port: u16,
+    #[partial(nested = PartialLog)]
+    log: Log,
+}
+
+fn main() {
+    let defaults = PartialConfig {
+        host: Some("localhost".to_string()),
+        port: Some(80),
+        log: Some(PartialLog {
+            level: Some("info".to_string()),
+            file: None,
+        }),
+    };
+    let file = PartialConfig {
+        host: None,
+        port: Some(8080),
+        log: Some(PartialLog {
+            level: None,
+            file: Some(Some("app.log".to_string())),
+        }),
+    };
+    let env = PartialConfig {
+        host: Some("example.com".to_string()),
+        port: None,
+        log: None,
+    };
+
+    let merged = layer(vec![defaults, file, env]).unwrap();
+
+    assert_eq!(
+        merged,
+        PartialConfig {
+            host: Some("example.com".to_string()),
+            port: Some(8080),
+            log: Some(PartialLog {
+                level: Some("info".to_string()),
+                file: Some(Some("app.log".to_string())),
+            }),
+        }
+    );
+
+    let config = PartialConfig {
+        host: None,
+        port: Some(443),
+        log: None,
+    }
+    .resolve(merged.into());
+
+    assert_eq!(
+        config,
+        Config {
+            host: "example.com".to_string(),
+            port: 443,
+            log: Log {
+                level: "info".to_string(),
+                file: Some("app.log".to_string()),
+            },
+        }
+    );
+}
@@ -0,0 +1,35 @@
+use utility_types::Partial;
+
+#[derive(Partial)]
+#[partial(ident = PartialUser, merge_patch)]
+pub struct User {
+    #[partial(skip)]
+    id: u64,
+    name: String,
+    age: u8,
+    email: Option<String>,
+}
+
+fn main() {
+    assert_eq!(PartialUser::FIELD_NAMES, &["name", "age", "email"]);
+
+    let empty = PartialUser {
+        name: None,
+        age: None,
+        email: PartialUserPatch::Unchanged,
+    };
+
+    assert!(empty.is_empty());
+    assert_eq!(empty.len(), 0);
+    assert!(empty.set_field_names().is_empty());
+
+    let patch = PartialUser {
+        name: Some("Alice".to_string()),
+        age: None,
+        email: PartialUserPatch::Clear,
+    };
+
+    assert!(!patch.is_empty());
+    assert_eq!(patch.len(), 2);
+    assert_eq!(patch.set_field_names(), vec!["name", "email"]);
+}
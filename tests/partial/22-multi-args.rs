@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use utility_types::Partial;
+
+#[derive(Partial, Serialize, Deserialize)]
+#[partial(ident = PartialA, derive(Debug, PartialEq), forward_attrs())]
+#[partial(arg(ident = PatchA, derive(Debug, PartialEq, Deserialize), forward_attrs(serde)))]
+#[partial(arg(ident = DefaultA, derive(Debug, PartialEq), try_from))]
+#[serde(rename_all = "UPPERCASE")]
+pub struct A {
+    #[partial(arg(ident = DefaultA, default = 42))]
+    a: usize,
+    #[partial(arg(ident = PatchA, flatten_option))]
+    b: Option<usize>,
+    #[partial(skip, default = String::from("c"), arg(ident = PatchA, skip = false))]
+    c: String,
+}
+
+fn main() {
+    let a: PartialA = PartialA {
+        a: Some(0),
+        b: Some(None),
+    };
+    assert_eq!(a.len(), 2);
+
+    let patch: PatchA = serde_json::from_str(r#"{"A":1,"B":2}"#).unwrap();
+    assert_eq!(
+        patch,
+        PatchA {
+            a: Some(1),
+            b: Some(2),
+            c: None,
+        }
+    );
+
+    let a = A::try_from(DefaultA {
+        a: None,
+        b: Some(Some(1)),
+    })
+    .unwrap();
+    assert_eq!(a.a, 42);
+    assert_eq!(a.b, Some(1));
+    assert_eq!(a.c, "c");
+}
@@ -0,0 +1,105 @@
+use utility_types::Partial;
+
+#[derive(Debug, Clone, PartialEq, Partial)]
+#[partial(ident = UpdateUser, derive(Debug, PartialEq), fields(name, email), diff)]
+#[partial(arg(ident = PatchUser, derive(Debug, PartialEq), try_from))]
+pub struct User {
+    id: u64,
+    name: String,
+    email: Option<String>,
+    #[partial(arg(ident = PatchUser, keep))]
+    version: u32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Partial)]
+#[partial(ident = PartialAddress, derive(Debug, PartialEq))]
+pub struct Address {
+    city: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Partial)]
+#[partial(ident = PartialProfile, derive(Debug, PartialEq))]
+#[partial(arg(ident = RenameProfile, derive(Debug, PartialEq), fields(name)))]
+pub struct Profile {
+    name: String,
+    #[partial(nested = PartialAddress)]
+    address: Address,
+}
+
+fn main() {
+    let mut user = User {
+        id: 1,
+        name: "a".to_string(),
+        email: None,
+        version: 1,
+    };
+
+    let update = UpdateUser {
+        id: 1,
+        name: Some("b".to_string()),
+        email: None,
+        version: 2,
+    };
+    assert_eq!(update.len(), 1);
+    assert_eq!(update.set_field_names(), vec!["name"]);
+    assert!(UpdateUser {
+        id: 1,
+        name: None,
+        email: None,
+        version: 2,
+    }
+    .is_empty());
+
+    update.apply_to(&mut user);
+    assert_eq!(
+        user,
+        User {
+            id: 1,
+            name: "b".to_string(),
+            email: None,
+            version: 2,
+        }
+    );
+
+    let partial: UpdateUser = user.clone().into();
+    assert_eq!(partial.email, Some(None));
+    assert_eq!(User::from(partial), user);
+
+    let mut changed = user.clone();
+    changed.email = Some("b@example.com".to_string());
+    assert_eq!(
+        UpdateUser::diff(&user, &changed),
+        UpdateUser {
+            id: 1,
+            name: None,
+            email: Some(Some("b@example.com".to_string())),
+            version: 2,
+        }
+    );
+
+    let err = User::try_from(PatchUser {
+        id: None,
+        name: Some("c".to_string()),
+        email: None,
+        version: 3,
+    })
+    .unwrap_err();
+    assert_eq!(err.missing_fields, vec!["id", "email"]);
+
+    let address = Address {
+        city: "Paris".to_string(),
+    };
+    let rename = RenameProfile {
+        name: Some("d".to_string()),
+        address: address.clone(),
+    };
+    assert_eq!(rename.set_field_names(), vec!["name"]);
+    assert_eq!(
+        Profile::from(rename),
+        Profile {
+            name: "d".to_string(),
+            address,
+        }
+    );
+    assert_eq!(PartialProfile::FIELD_NAMES, &["name", "address"]);
+}
@@ -0,0 +1,10 @@
+use utility_types::Partial;
+
+#[derive(Partial)]
+#[partial(ident = PartialA, fields(a, c))]
+pub struct A {
+    a: usize,
+    b: usize,
+}
+
+fn main() {}
@@ -0,0 +1,150 @@
+use std::fmt::Debug;
+
+use serde::{Deserialize, Serialize};
+use utility_types::Partial;
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialA, derive(Debug, PartialEq), diff, builder)]
+pub struct A<'a, T: Clone + Default, U, const N: usize>
+where
+    U: Debug + Default + Clone + PartialEq,
+    T: PartialEq,
+{
+    a: &'a str,
+    b: T,
+    c: Option<U>,
+    #[partial(default = [0; N])]
+    d: [u8; N],
+}
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialB, derive(Debug, PartialEq), builder(typestate))]
+pub struct B<T: Clone>
+where
+    T: Debug + Default,
+{
+    a: T,
+    #[partial(default = 1)]
+    b: usize,
+}
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialC, derive(Debug, PartialEq), try_from)]
+pub struct C<'a, T>(&'a T, Option<T>)
+where
+    T: Debug;
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialD, derive(Debug, PartialEq), diff, builder)]
+pub struct D<T: Default + PartialEq> {
+    #[partial(skip)]
+    id: T,
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialE, derive(Debug, PartialEq))]
+pub struct E<'a>(#[partial(skip)] &'a str, u8);
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialF, derive(Debug, PartialEq), diff)]
+pub struct F<T: Into<U> + Clone + Default + PartialEq, U>
+where
+    U: Default + Clone + PartialEq,
+{
+    value: T,
+    #[partial(skip)]
+    converted: U,
+}
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialRec, derive(Debug, PartialEq, Serialize, Deserialize))]
+pub struct Rec<Id: Default> {
+    #[partial(skip)]
+    id: Id,
+    name: String,
+}
+
+fn main() {
+    let a = A::<'_, String, usize, 2> {
+        a: "a",
+        b: "b".to_string(),
+        c: Some(1),
+        d: [1, 2],
+    };
+
+    let partial: PartialA<'_, String, usize, 2> = PartialA {
+        a: Some("a"),
+        b: None,
+        c: None,
+        d: None,
+    };
+    let resolved = partial.resolve(A {
+        a: "",
+        b: "b".to_string(),
+        c: Some(1),
+        d: [1, 2],
+    });
+    assert_eq!(resolved, a);
+
+    let built = A::<'_, String, usize, 2>::builder().a("a").b("b".to_string()).build();
+    assert_eq!(
+        built,
+        A {
+            a: "a",
+            b: "b".to_string(),
+            c: None,
+            d: [0, 0],
+        }
+    );
+    assert_eq!(PartialA::diff(&built, &a).len(), 2);
+
+    let b = B::builder().a("a").build();
+    assert_eq!(b, B { a: "a", b: 1 });
+
+    let value = 1;
+    let c = C::try_from(PartialC(Some(&value), Some(None))).unwrap();
+    assert_eq!(c, C(&1, None));
+
+    let d = D::<u64>::builder().name("d".to_string()).build();
+    assert_eq!(
+        d,
+        D {
+            id: 0,
+            name: "d".to_string(),
+        }
+    );
+    let partial = PartialD { name: None };
+    assert!(partial.merge(PartialD::diff(&d, &d)).is_empty());
+    let mut d = PartialD {
+        name: Some("e".to_string()),
+    }
+    .resolve(d);
+    PartialD { name: None }.apply_to(&mut d);
+    assert_eq!(d.name, "e");
+
+    let e = E::from(PartialE(Some(1)));
+    assert_eq!(e, E("", 1));
+
+    let mut f = F::<u8, u16> {
+        value: 1,
+        converted: 1,
+    };
+    let partial = PartialF::diff(&f, &F { value: 2, converted: 0 });
+    assert_eq!(partial, PartialF { value: Some(2) });
+    partial.apply_to(&mut f);
+    assert_eq!(f.value, 2);
+
+    let partial = PartialRec {
+        name: Some("n".to_string()),
+    };
+    assert_eq!(serde_json::to_string(&partial).unwrap(), r#"{"name":"n"}"#);
+    let partial: PartialRec = serde_json::from_str(r#"{"name":"x"}"#).unwrap();
+    assert_eq!(
+        Rec::<u32>::from(partial),
+        Rec {
+            id: 0,
+            name: "x".to_string(),
+        }
+    );
+}
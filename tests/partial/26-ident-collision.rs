@@ -0,0 +1,13 @@
+use utility_types::Partial;
+
+#[derive(Partial)]
+#[partial(ident = PartialArticle)]
+#[partial(arg(ident = PartialArticle), arg(ident = Article))]
+struct Article {
+    #[partial(arg(ident = PartailArticle, skip))]
+    title: String,
+    #[partial(arg(ident = UpdateArticle, skip))]
+    author: String,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use utility_types::Partial;
+
+#[derive(Partial)]
+#[partial(ident = PartialArticle, builder)]
+#[partial(arg(ident = UpdateArticle, builder(ident = ArticleUpdater)))]
+struct Article {
+    title: String,
+}
+
+fn main() {}
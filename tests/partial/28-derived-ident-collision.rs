@@ -0,0 +1,15 @@
+use utility_types::Partial;
+
+#[derive(Partial)]
+#[partial(ident = PartialArticle, try_from)]
+#[partial(
+    arg(ident = PartialArticleError),
+    arg(ident = UpdateArticle, merge_patch(ident = Article)),
+    arg(ident = CreateArticle, builder(ident = UpdateArticle))
+)]
+struct Article {
+    title: String,
+    author: Option<String>,
+}
+
+fn main() {}
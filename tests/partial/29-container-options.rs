@@ -0,0 +1,15 @@
+use utility_types::Partial;
+
+#[derive(Partial)]
+#[partial(diff, try_from, arg(ident = PartialA), arg(ident = PartialB))]
+pub struct A {
+    a: u8,
+}
+
+#[derive(Partial)]
+#[partial(forward_attrs(), arg(ident = PartialC, derive(Debug)))]
+pub struct C {
+    c: u8,
+}
+
+fn main() {}
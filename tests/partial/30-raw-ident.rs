@@ -0,0 +1,31 @@
+use utility_types::Partial;
+
+#[derive(Debug, PartialEq, Partial)]
+#[partial(ident = PartialItem, derive(Debug, PartialEq), try_from)]
+pub struct Item {
+    r#type: String,
+    r#ref: Option<u8>,
+}
+
+fn main() {
+    let partial = PartialItem {
+        r#type: Some("a".to_string()),
+        r#ref: Some(None),
+    };
+    assert_eq!(
+        Item::try_from(partial),
+        Ok(Item {
+            r#type: "a".to_string(),
+            r#ref: None,
+        })
+    );
+
+    let partial = PartialItem {
+        r#type: None,
+        r#ref: None,
+    };
+    assert_eq!(
+        Item::try_from(partial).unwrap_err().missing_fields,
+        vec!["type", "ref"]
+    );
+}
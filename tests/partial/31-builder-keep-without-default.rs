@@ -0,0 +1,12 @@
+use utility_types::Partial;
+
+pub struct Id(u32);
+
+#[derive(Partial)]
+#[partial(ident = PartialJob, builder, fields(name))]
+pub struct Job {
+    name: String,
+    id: Id,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use utility_types::Partial;
+
+#[derive(Clone, PartialEq, Partial)]
+#[partial(ident = PartialContact, diff)]
+pub struct Contact {
+    name: String,
+    #[partial(flatten_option)]
+    email: Option<String>,
+}
+
+fn main() {}
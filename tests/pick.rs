@@ -11,6 +11,16 @@ fn pick() {
     t.compile_fail("tests/pick/05-no-fields.rs");
     t.compile_fail("tests/pick/06-empty-fields.rs");
     t.compile_fail("tests/pick/07-field-not-ident.rs");
-    t.pass("tests/pick/08-field-not-exist.rs");
+    t.compile_fail("tests/pick/08-field-not-exist.rs");
     t.pass("tests/pick/09-forward-attrs.rs");
+    t.pass("tests/pick/10-tuple-struct.rs");
+    t.pass("tests/pick/11-generics.rs");
+    t.pass("tests/pick/12-unused-generics.rs");
+    t.pass("tests/pick/13-lenient.rs");
+    t.compile_fail("tests/pick/14-multiple-errors.rs");
+    t.compile_fail("tests/pick/15-ident-collision.rs");
+    t.pass("tests/pick/16-split.rs");
+    t.compile_fail("tests/pick/17-split-collision.rs");
+    t.pass("tests/pick/18-assign.rs");
+    t.pass("tests/pick/19-views.rs");
 }
@@ -1,7 +1,10 @@
 use utility_types::Pick;
 
 #[derive(Pick, Debug, Clone, PartialEq)]
-#[pick(arg(ident = Meta, fields(foo), derive(Debug, PartialEq)))]
+#[pick(
+    arg(ident = Meta, fields(foo), derive(Debug, PartialEq)),
+    arg(ident = Header, fields(titel, author, author), derive(Debug, PartialEq))
+)]
 struct Article {
     title: String,
     author: String,
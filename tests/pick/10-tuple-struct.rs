@@ -0,0 +1,23 @@
+use utility_types::Pick;
+
+#[derive(Pick, Debug, Clone, PartialEq)]
+#[pick(arg(ident = Meta, fields(0, 2), derive(Debug, PartialEq)))]
+struct Article(String, String, Vec<String>);
+
+fn main() {
+    let article = Article(
+        "Hello, world!".to_string(),
+        "This is an article.".to_string(),
+        vec!["hello".to_string(), "world".to_string()],
+    );
+
+    let meta: Meta = article.into();
+
+    assert_eq!(
+        meta,
+        Meta(
+            "Hello, world!".to_string(),
+            vec!["hello".to_string(), "world".to_string()],
+        )
+    );
+}
@@ -0,0 +1,28 @@
+use std::fmt::Debug;
+
+use utility_types::Pick;
+
+#[derive(Clone, Pick)]
+#[pick(arg(ident = PickAB, fields(a, b), derive(Debug, PartialEq)))]
+pub struct A<'a, T: Clone, U, const N: usize>
+where
+    U: Debug,
+{
+    a: &'a T,
+    b: [U; N],
+}
+
+#[derive(Clone, Pick)]
+#[pick(arg(ident = PickB, fields(0, 1), derive(Debug, PartialEq)))]
+pub struct B<T>(T, Option<T>)
+where
+    T: Debug;
+
+fn main() {
+    let a = A { a: &1, b: [2, 3] };
+    let pick: PickAB<'_, i32, i32, 2> = a.into();
+    assert_eq!(pick, PickAB { a: &1, b: [2, 3] });
+
+    let pick: PickB<i32> = B(1, None).into();
+    assert_eq!(pick, PickB(1, None));
+}
@@ -0,0 +1,49 @@
+use std::fmt::Debug;
+
+use utility_types::Pick;
+
+#[derive(Clone, Pick)]
+#[pick(
+    arg(ident = PickA, fields(a), derive(Debug, PartialEq)),
+    arg(ident = PickBC, fields(b, c), derive(Debug, PartialEq)),
+    arg(ident = PickD, fields(d), derive(Debug, PartialEq))
+)]
+pub struct A<'a, 'b: 'a, T: Clone + 'b, U: Into<T>, const N: usize>
+where
+    T: Debug,
+    U: Debug + 'a,
+{
+    a: &'a str,
+    b: &'b T,
+    c: [U; N],
+    d: String,
+}
+
+#[derive(Clone, Pick)]
+#[pick(arg(ident = PickFirst, fields(0), derive(Debug, PartialEq)))]
+pub struct B<T, U>(T, U)
+where
+    T: Debug,
+    U: Debug;
+
+fn main() {
+    let a = A::<'_, '_, i64, i32, 2> {
+        a: "a",
+        b: &1,
+        c: [2, 3],
+        d: "d".to_string(),
+    };
+
+    let pick: PickA<'_> = a.clone().into();
+    assert_eq!(pick, PickA { a: "a" });
+
+    // `'b` is kept without the `'b: 'a` bound, `U` keeps `Into<T>` since `T` is used
+    let pick: PickBC<'_, i64, i32, 2> = a.clone().into();
+    assert_eq!(pick, PickBC { b: &1, c: [2, 3] });
+
+    let pick: PickD = a.into();
+    assert_eq!(pick, PickD { d: "d".to_string() });
+
+    let pick: PickFirst<i32> = B(1, "unused").into();
+    assert_eq!(pick, PickFirst(1));
+}
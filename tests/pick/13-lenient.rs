@@ -0,0 +1,16 @@
+use utility_types::Pick;
+
+#[derive(Pick, Debug, Clone, PartialEq)]
+#[pick(arg(ident = Meta, fields(title, foo), derive(Debug, PartialEq), lenient))]
+struct Article {
+    title: String,
+    author: String,
+}
+
+fn main() {
+    let article = Article {
+        title: "title".to_string(),
+        author: "author".to_string(),
+    };
+    let _meta: Meta = article.into();
+}
@@ -0,0 +1,16 @@
+use utility_types::Pick;
+
+#[derive(Pick, Debug, Clone, PartialEq)]
+#[pick(
+    arg(fields(title, author), derive(Debug, PartialEq)),
+    arg(ident = Content, fields(content, "tags")),
+    arg(ident = Author)
+)]
+struct Article {
+    title: String,
+    author: String,
+    content: String,
+    tags: Vec<String>,
+}
+
+fn main() {}
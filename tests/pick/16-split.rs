@@ -0,0 +1,57 @@
+use utility_types::Pick;
+
+#[derive(Debug, Clone, PartialEq, Pick)]
+#[pick(
+    arg(ident = Header, fields(title, author), derive(Debug, PartialEq), split),
+    arg(ident = PickTags, fields(tags), derive(Debug, PartialEq), split(ident = Untagged)),
+    forward_attrs()
+)]
+pub struct Article<T> {
+    title: String,
+    author: String,
+    content: String,
+    tags: Vec<T>,
+}
+
+#[derive(Debug, Clone, PartialEq, Pick)]
+#[pick(arg(ident = PickFirst, fields(0), derive(Debug, PartialEq), split), forward_attrs())]
+pub struct Pair<'a>(&'a str, u8, bool);
+
+fn main() {
+    let article = Article {
+        title: "title".to_string(),
+        author: "author".to_string(),
+        content: "content".to_string(),
+        tags: vec![1, 2],
+    };
+
+    let (header, rest) = article.clone().split_header();
+    assert_eq!(
+        header,
+        Header {
+            title: "title".to_string(),
+            author: "author".to_string(),
+        }
+    );
+    assert_eq!(
+        rest,
+        HeaderRest {
+            content: "content".to_string(),
+            tags: vec![1, 2],
+        }
+    );
+    assert_eq!(Article::from((header, rest)), article);
+
+    let rest: HeaderRest<i32> = article.clone().into();
+    assert_eq!(rest.tags, vec![1, 2]);
+
+    let (tags, untagged): (PickTags<i32>, Untagged) = article.clone().split_pick_tags();
+    assert_eq!(tags, PickTags { tags: vec![1, 2] });
+    assert_eq!(Article::from((tags, untagged)), article);
+
+    let pair = Pair("a", 1, true);
+    let (first, rest) = pair.clone().split_pick_first();
+    assert_eq!(first, PickFirst("a"));
+    assert_eq!(rest, PickFirstRest(1, true));
+    assert_eq!(Pair::from((first, rest)), pair);
+}
@@ -0,0 +1,13 @@
+use utility_types::Pick;
+
+#[derive(Pick)]
+#[pick(
+    arg(ident = Header, fields(title), split(ident = Body)),
+    arg(ident = Body, fields(content))
+)]
+pub struct Article {
+    title: String,
+    content: String,
+}
+
+fn main() {}
@@ -0,0 +1,44 @@
+use utility_types::Pick;
+
+#[derive(Debug, Clone, PartialEq, Pick)]
+#[pick(
+    arg(ident = ArticleForm, fields(title, tags), derive(Debug, PartialEq), assign),
+    forward_attrs()
+)]
+pub struct Article<'a, T> {
+    id: u64,
+    title: &'a str,
+    content: String,
+    tags: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, Pick)]
+#[pick(arg(ident = PickSecond, fields(1), assign), forward_attrs())]
+pub struct Pair(u8, bool);
+
+fn main() {
+    let mut article = Article {
+        id: 1,
+        title: "title",
+        content: "content".to_string(),
+        tags: vec![1],
+    };
+
+    article.assign_article_form(ArticleForm {
+        title: "new title",
+        tags: vec![2, 3],
+    });
+    assert_eq!(
+        article,
+        Article {
+            id: 1,
+            title: "new title",
+            content: "content".to_string(),
+            tags: vec![2, 3],
+        }
+    );
+
+    let mut pair = Pair(1, false);
+    pair.assign_pick_second(PickSecond(true));
+    assert_eq!(pair, Pair(1, true));
+}
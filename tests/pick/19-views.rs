@@ -0,0 +1,75 @@
+use serde::Serialize;
+use utility_types::Pick;
+
+#[derive(Debug, Clone, PartialEq, Pick)]
+#[pick(
+    arg(ident = Header, fields(title, tags), ref_view(derive(Debug, PartialEq)), mut_view),
+    arg(ident = PickId, fields(id), ref_view(ident = IdView)),
+    forward_attrs()
+)]
+pub struct Article<'a, T> {
+    id: u64,
+    title: &'a str,
+    content: String,
+    tags: Vec<T>,
+}
+
+#[derive(Serialize, Pick)]
+#[pick(arg(
+    ident = Summary,
+    fields(title, tags),
+    derive(Serialize),
+    ref_view(derive(Serialize))
+))]
+#[serde(rename_all = "UPPERCASE")]
+pub struct Post {
+    title: String,
+    content: String,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Pick)]
+#[pick(arg(ident = PickFirst, fields(0), ref_view, mut_view), forward_attrs())]
+pub struct Pair(u8, bool);
+
+fn main() {
+    let mut article = Article {
+        id: 1,
+        title: "title",
+        content: "content".to_string(),
+        tags: vec![1, 2],
+    };
+
+    let header: HeaderRef<'_, '_, i32> = article.as_header();
+    assert_eq!(
+        header,
+        HeaderRef {
+            title: &"title",
+            tags: &vec![1, 2],
+        }
+    );
+
+    let header = article.as_header_mut();
+    *header.title = "new title";
+    header.tags.push(3);
+    assert_eq!(article.title, "new title");
+    assert_eq!(article.tags, vec![1, 2, 3]);
+
+    let IdView { id } = article.as_pick_id();
+    assert_eq!(*id, 1);
+
+    let post = Post {
+        title: "title".to_string(),
+        content: "content".to_string(),
+        tags: vec!["a".to_string()],
+    };
+    assert_eq!(
+        serde_json::to_string(&post.as_summary()).unwrap(),
+        r#"{"TITLE":"title","TAGS":["a"]}"#
+    );
+
+    let mut pair = Pair(1, false);
+    assert_eq!(*pair.as_pick_first().0, 1);
+    *pair.as_pick_first_mut().0 = 2;
+    assert_eq!(pair, Pair(2, false));
+}
@@ -11,4 +11,20 @@ fn required() {
     t.pass("tests/required/05-ident-str.rs");
     t.pass("tests/required/06-derive-empty.rs");
     t.pass("tests/required/07-forward-attrs.rs");
+    t.pass("tests/required/08-tuple-struct.rs");
+    t.pass("tests/required/09-multi-args.rs");
+    t.pass("tests/required/10-selective.rs");
+    t.pass("tests/required/11-from-required.rs");
+    t.pass("tests/required/12-try-from.rs");
+    t.compile_fail("tests/required/13-default-non-option.rs");
+    t.pass("tests/required/14-unwrap.rs");
+    t.pass("tests/required/15-deep-nested.rs");
+    t.pass("tests/required/16-try-from-partial.rs");
+    t.compile_fail("tests/required/17-try-from-partial-unknown.rs");
+    t.pass("tests/required/18-generics.rs");
+    t.compile_fail("tests/required/19-ident-collision.rs");
+    t.compile_fail("tests/required/20-derived-ident-collision.rs");
+    t.compile_fail("tests/required/21-unwrap-errors.rs");
+    t.compile_fail("tests/required/22-container-options.rs");
+    t.pass("tests/required/23-raw-ident.rs");
 }
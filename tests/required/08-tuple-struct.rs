@@ -0,0 +1,11 @@
+use utility_types::Required;
+
+#[derive(Required)]
+#[required(ident = RequiredA, derive(Debug, PartialEq))]
+pub struct A(pub usize, pub Option<usize>);
+
+fn main() {
+    let a = RequiredA(0, 1);
+
+    assert_eq!(a, RequiredA(0, 1));
+}
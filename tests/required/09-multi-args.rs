@@ -0,0 +1,20 @@
+use serde::Serialize;
+use utility_types::Required;
+
+#[derive(Required, Serialize)]
+#[required(ident = RequiredA, derive(Debug, PartialEq), forward_attrs())]
+#[required(arg(ident = SerializedA, derive(Debug, PartialEq, Serialize), forward_attrs(serde)))]
+#[serde(rename_all = "UPPERCASE")]
+pub struct A {
+    a: usize,
+    #[required(arg(ident = SerializedA, forward_attrs(serde)))]
+    #[serde(rename = "bee")]
+    b: Option<usize>,
+}
+
+fn main() {
+    let _ra = RequiredA { a: 0, b: 1 };
+
+    let sa = SerializedA { a: 0, b: 1 };
+    assert_eq!(serde_json::to_string(&sa).unwrap(), r#"{"A":0,"bee":1}"#);
+}
@@ -0,0 +1,35 @@
+use utility_types::Required;
+
+#[derive(Debug, PartialEq, Required)]
+#[required(ident = RequiredInner, derive(Debug, PartialEq))]
+pub struct Inner {
+    a: Option<u8>,
+}
+
+#[derive(Required)]
+#[required(ident = CreateUser, derive(Debug, PartialEq), fields(name))]
+#[required(arg(ident = RequiredUser, derive(Debug, PartialEq)))]
+pub struct User {
+    name: Option<String>,
+    email: Option<String>,
+    #[required(arg(ident = RequiredUser, keep))]
+    nickname: Option<String>,
+    #[required(nested = RequiredInner)]
+    inner: Inner,
+}
+
+fn main() {
+    let _create = CreateUser {
+        name: "a".to_string(),
+        email: None,
+        nickname: None,
+        inner: Inner { a: None },
+    };
+
+    let _required = RequiredUser {
+        name: "a".to_string(),
+        email: "a@example.com".to_string(),
+        nickname: None,
+        inner: RequiredInner { a: 1 },
+    };
+}
@@ -0,0 +1,37 @@
+use utility_types::Required;
+
+#[derive(Debug, PartialEq, Required)]
+#[required(ident = RequiredA, derive(Debug, PartialEq), forward_attrs())]
+#[required(arg(ident = RequiredAB, fields(b)))]
+pub struct A {
+    a: usize,
+    b: Option<usize>,
+    c: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Required)]
+#[required(ident = RequiredB)]
+pub struct B(usize, Option<usize>);
+
+fn main() {
+    let a: A = RequiredA {
+        a: 0,
+        b: 1,
+        c: "c".to_string(),
+    }
+    .into();
+    assert_eq!(
+        a,
+        A {
+            a: 0,
+            b: Some(1),
+            c: Some("c".to_string()),
+        }
+    );
+
+    let a: A = RequiredAB { a: 0, b: 1, c: None }.into();
+    assert_eq!(a, A { a: 0, b: Some(1), c: None });
+
+    let b: B = RequiredB(0, 1).into();
+    assert_eq!(b, B(0, Some(1)));
+}
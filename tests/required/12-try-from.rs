@@ -0,0 +1,50 @@
+use utility_types::Required;
+
+#[derive(Debug, PartialEq, Required)]
+#[required(ident = RequiredA, derive(Debug, PartialEq), try_from)]
+pub struct A {
+    a: usize,
+    b: Option<usize>,
+    c: Option<String>,
+    #[required(default = 4)]
+    d: Option<usize>,
+}
+
+#[derive(Required)]
+#[required(ident = RequiredB, derive(Debug, PartialEq), try_from)]
+pub struct B(usize, Option<usize>);
+
+fn main() {
+    let a = RequiredA::try_from(A {
+        a: 0,
+        b: Some(1),
+        c: Some("c".to_string()),
+        d: None,
+    })
+    .unwrap();
+    assert_eq!(
+        a,
+        RequiredA {
+            a: 0,
+            b: 1,
+            c: "c".to_string(),
+            d: 4,
+        }
+    );
+
+    let err = RequiredA::try_from(A {
+        a: 0,
+        b: None,
+        c: None,
+        d: None,
+    })
+    .unwrap_err();
+    assert_eq!(err.missing_fields, vec!["b", "c"]);
+    assert_eq!(err.to_string(), "missing fields: b, c");
+
+    assert_eq!(RequiredB::try_from(B(0, Some(1))), Ok(RequiredB(0, 1)));
+    assert_eq!(
+        RequiredB::try_from(B(0, None)).unwrap_err().missing_fields,
+        vec!["1"]
+    );
+}
@@ -0,0 +1,11 @@
+use utility_types::Required;
+
+#[derive(Required)]
+#[required(ident = RequiredA, try_from)]
+pub struct A {
+    #[required(default = 0)]
+    a: usize,
+    b: Option<usize>,
+}
+
+fn main() {}
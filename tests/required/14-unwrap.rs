@@ -0,0 +1,99 @@
+use utility_types::Required;
+
+pub type Maybe<T> = Option<T>;
+
+#[derive(Debug, PartialEq)]
+pub enum MaybeUndefined<T> {
+    Undefined,
+    Null,
+    Value(T),
+}
+
+impl<T> MaybeUndefined<T> {
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Self::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<T> From<T> for MaybeUndefined<T> {
+    fn from(value: T) -> Self {
+        Self::Value(value)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Nullable<T>(Option<T>);
+
+impl<T> From<T> for Nullable<T> {
+    fn from(value: T) -> Self {
+        Self(Some(value))
+    }
+}
+
+impl<T> From<Nullable<T>> for Option<T> {
+    fn from(value: Nullable<T>) -> Self {
+        value.0
+    }
+}
+
+#[derive(Debug, PartialEq, Required)]
+#[required(
+    ident = RequiredA,
+    derive(Debug, PartialEq),
+    try_from,
+    unwrap(Maybe, MaybeUndefined = MaybeUndefined::into_option)
+)]
+pub struct A {
+    a: Maybe<usize>,
+    b: MaybeUndefined<usize>,
+    #[required(unwrap(Nullable))]
+    c: Nullable<usize>,
+    #[required(default = 4)]
+    d: MaybeUndefined<usize>,
+    e: Option<usize>,
+}
+
+fn main() {
+    let a = RequiredA::try_from(A {
+        a: Some(1),
+        b: MaybeUndefined::Value(2),
+        c: Nullable(Some(3)),
+        d: MaybeUndefined::Null,
+        e: Some(5),
+    })
+    .unwrap();
+    assert_eq!(
+        a,
+        RequiredA {
+            a: 1,
+            b: 2,
+            c: 3,
+            d: 4,
+            e: 5,
+        }
+    );
+
+    let err = RequiredA::try_from(A {
+        a: None,
+        b: MaybeUndefined::Undefined,
+        c: Nullable(None),
+        d: MaybeUndefined::Undefined,
+        e: None,
+    })
+    .unwrap_err();
+    assert_eq!(err.missing_fields, vec!["a", "b", "c", "e"]);
+
+    assert_eq!(
+        A::from(a),
+        A {
+            a: Some(1),
+            b: MaybeUndefined::Value(2),
+            c: Nullable(Some(3)),
+            d: MaybeUndefined::Value(4),
+            e: Some(5),
+        }
+    );
+}
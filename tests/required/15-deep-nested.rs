@@ -0,0 +1,71 @@
+use utility_types::Required;
+
+pub type Maybe<T> = Option<T>;
+
+#[derive(Debug, PartialEq, Required)]
+#[required(ident = RequiredInner, derive(Debug, PartialEq), try_from)]
+pub struct Inner {
+    a: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Required)]
+#[required(ident = RequiredOuter, derive(Debug, PartialEq), try_from, unwrap(Maybe))]
+pub struct Outer {
+    #[required(deep)]
+    a: Option<Option<usize>>,
+    #[required(deep)]
+    b: Option<Maybe<Option<usize>>>,
+    c: Option<Option<usize>>,
+    #[required(nested = RequiredInner)]
+    d: Option<Inner>,
+    #[required(nested = RequiredInner)]
+    e: Inner,
+    #[required(deep, nested = RequiredInner, default = RequiredInner { a: 0 })]
+    f: Option<Option<Inner>>,
+}
+
+fn main() {
+    let outer = Outer {
+        a: Some(Some(1)),
+        b: Some(Some(Some(2))),
+        c: Some(None),
+        d: Some(Inner { a: Some(4) }),
+        e: Inner { a: Some(5) },
+        f: Some(None),
+    };
+    let required = RequiredOuter::try_from(outer).unwrap();
+    assert_eq!(
+        required,
+        RequiredOuter {
+            a: 1,
+            b: 2,
+            c: None,
+            d: RequiredInner { a: 4 },
+            e: RequiredInner { a: 5 },
+            f: RequiredInner { a: 0 },
+        }
+    );
+
+    assert_eq!(
+        Outer::from(required),
+        Outer {
+            a: Some(Some(1)),
+            b: Some(Some(Some(2))),
+            c: Some(None),
+            d: Some(Inner { a: Some(4) }),
+            e: Inner { a: Some(5) },
+            f: Some(Some(Inner { a: Some(0) })),
+        }
+    );
+
+    let err = RequiredOuter::try_from(Outer {
+        a: Some(None),
+        b: Some(Some(None)),
+        c: None,
+        d: Some(Inner { a: None }),
+        e: Inner { a: None },
+        f: Some(Some(Inner { a: None })),
+    })
+    .unwrap_err();
+    assert_eq!(err.missing_fields, vec!["a", "b", "c", "d", "e", "f"]);
+}
@@ -0,0 +1,112 @@
+use utility_types::{Partial, Required};
+
+#[derive(Debug, Default, PartialEq, Partial, Required)]
+#[partial(ident = PartialInner, forward_attrs())]
+#[required(
+    ident = RequiredInner,
+    derive(Debug, PartialEq),
+    forward_attrs(),
+    try_from_partial = PartialInner
+)]
+pub struct Inner {
+    a: usize,
+    b: Option<usize>,
+}
+
+#[derive(Debug, PartialEq, Partial, Required)]
+#[partial(ident = PartialOuter, forward_attrs())]
+#[partial(arg(ident = PatchOuter, merge_patch))]
+#[required(
+    ident = RequiredOuter,
+    derive(Debug, PartialEq),
+    forward_attrs(),
+    try_from_partial = PartialOuter
+)]
+#[required(arg(ident = PatchedOuter, derive(Debug, PartialEq), try_from_partial = PatchOuter))]
+pub struct Outer {
+    a: usize,
+    b: Option<usize>,
+    #[partial(flatten_option)]
+    c: Option<usize>,
+    #[partial(nested = PartialInner)]
+    #[required(nested = RequiredInner)]
+    d: Inner,
+    #[partial(skip, default = Some(5))]
+    e: Option<usize>,
+    #[partial(keep)]
+    #[required(default = 6)]
+    f: Option<usize>,
+}
+
+fn main() {
+    let partial = PartialOuter {
+        a: Some(0),
+        b: Some(Some(1)),
+        c: Some(2),
+        d: Some(PartialInner {
+            a: Some(3),
+            b: Some(Some(4)),
+        }),
+        f: None,
+    };
+    assert_eq!(
+        RequiredOuter::try_from(partial),
+        Ok(RequiredOuter {
+            a: 0,
+            b: 1,
+            c: 2,
+            d: RequiredInner { a: 3, b: 4 },
+            e: 5,
+            f: 6,
+        })
+    );
+
+    let partial = PartialOuter {
+        a: None,
+        b: Some(None),
+        c: None,
+        d: Some(PartialInner {
+            a: Some(3),
+            b: None,
+        }),
+        f: Some(7),
+    };
+    assert_eq!(
+        RequiredOuter::try_from(partial).unwrap_err().missing_fields,
+        vec!["a", "b", "c", "d"]
+    );
+
+    let patch = PatchOuter {
+        a: Some(0),
+        b: PatchOuterPatch::Set(1),
+        c: Some(2),
+        d: Some(PartialInner {
+            a: Some(3),
+            b: Some(Some(4)),
+        }),
+        f: None,
+    };
+    assert_eq!(
+        PatchedOuter::try_from(patch),
+        Ok(PatchedOuter {
+            a: 0,
+            b: 1,
+            c: 2,
+            d: RequiredInner { a: 3, b: 4 },
+            e: 5,
+            f: 6,
+        })
+    );
+
+    let patch = PatchOuter {
+        a: None,
+        b: PatchOuterPatch::Clear,
+        c: None,
+        d: None,
+        f: Some(7),
+    };
+    assert_eq!(
+        PatchedOuter::try_from(patch).unwrap_err().missing_fields,
+        vec!["a", "b", "c", "d"]
+    );
+}
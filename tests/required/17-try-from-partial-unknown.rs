@@ -0,0 +1,10 @@
+use utility_types::{Partial, Required};
+
+#[derive(Partial, Required)]
+#[partial(ident = PartialA, forward_attrs())]
+#[required(ident = RequiredA, try_from_partial = PartialB)]
+pub struct A {
+    a: Option<usize>,
+}
+
+fn main() {}
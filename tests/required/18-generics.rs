@@ -0,0 +1,86 @@
+use std::fmt::Debug;
+
+use utility_types::{Partial, Required};
+
+#[derive(Debug, PartialEq, Partial, Required)]
+#[partial(ident = PartialA, forward_attrs())]
+#[required(
+    ident = RequiredA,
+    derive(Debug, PartialEq),
+    forward_attrs(),
+    try_from,
+    try_from_partial = PartialA
+)]
+pub struct A<'a, T: Clone, const N: usize>
+where
+    T: Debug,
+{
+    a: Option<&'a str>,
+    b: Option<T>,
+    #[partial(default = [0; N])]
+    c: [u8; N],
+}
+
+#[derive(Debug, PartialEq, Required)]
+#[required(ident = RequiredB, derive(Debug, PartialEq), try_from)]
+pub struct B<T>(Option<T>)
+where
+    T: Debug;
+
+#[derive(Debug, PartialEq, Partial, Required)]
+#[partial(ident = PartialC, forward_attrs())]
+#[required(
+    ident = RequiredC,
+    derive(Debug, PartialEq),
+    forward_attrs(),
+    try_from_partial = PartialC
+)]
+pub struct C<Id: Default> {
+    #[partial(skip)]
+    #[required(keep)]
+    id: Id,
+    name: Option<String>,
+}
+
+fn main() {
+    let a = A {
+        a: Some("a"),
+        b: Some(1),
+        c: [1, 2],
+    };
+    let required = RequiredA::try_from(a).unwrap();
+    assert_eq!(
+        required,
+        RequiredA {
+            a: "a",
+            b: 1,
+            c: [1, 2],
+        }
+    );
+
+    let a: A<'_, usize, 2> = required.into();
+    assert_eq!(a.b, Some(1));
+
+    let partial = PartialA {
+        a: Some(None),
+        b: Some(Some(1)),
+        c: Some([1, 2]),
+    };
+    assert_eq!(
+        RequiredA::try_from(partial).unwrap_err().missing_fields,
+        vec!["a"]
+    );
+
+    assert_eq!(RequiredB::try_from(B(Some(1))), Ok(RequiredB(1)));
+
+    let partial = PartialC {
+        name: Some(Some("c".to_string())),
+    };
+    assert_eq!(
+        RequiredC::<u8>::try_from(partial),
+        Ok(RequiredC {
+            id: 0,
+            name: "c".to_string(),
+        })
+    );
+}
@@ -0,0 +1,13 @@
+use utility_types::Required;
+
+#[derive(Required)]
+#[required(ident = RequiredArticle)]
+#[required(arg(ident = RequiredArticle), arg(ident = Article))]
+struct Article {
+    #[required(arg(ident = RequriedArticle, keep))]
+    title: Option<String>,
+    #[required(arg(ident = CreateArticle, keep))]
+    author: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use utility_types::Required;
+
+#[derive(Required)]
+#[required(ident = RequiredArticle, try_from)]
+#[required(arg(ident = RequiredArticleError))]
+struct Article {
+    title: Option<String>,
+}
+
+fn main() {}
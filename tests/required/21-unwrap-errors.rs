@@ -0,0 +1,9 @@
+use utility_types::Required;
+
+#[derive(Required)]
+#[required(ident = RequiredArticle, unwrap(Maybe, "Nullable", 1))]
+struct Article {
+    title: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use utility_types::Required;
+
+#[derive(Required)]
+#[required(try_from, unwrap(Maybe), arg(ident = RequiredX))]
+pub struct X {
+    x: Option<u8>,
+}
+
+#[derive(Required)]
+#[required(forward_attrs(), arg(ident = RequiredY, derive(Debug)))]
+pub struct Y {
+    y: Option<u8>,
+}
+
+fn main() {}
@@ -0,0 +1,38 @@
+use utility_types::{Partial, Required};
+
+#[derive(Debug, PartialEq, Partial, Required)]
+#[partial(ident = PartialItem, forward_attrs())]
+#[required(
+    ident = RequiredItem,
+    derive(Debug, PartialEq),
+    forward_attrs(),
+    try_from,
+    try_from_partial = PartialItem
+)]
+pub struct Item {
+    r#type: Option<String>,
+    r#ref: Option<u8>,
+}
+
+fn main() {
+    let item = Item {
+        r#type: Some("a".to_string()),
+        r#ref: None,
+    };
+    assert_eq!(
+        RequiredItem::try_from(item).unwrap_err().missing_fields,
+        vec!["ref"]
+    );
+
+    let partial = PartialItem {
+        r#type: Some(Some("a".to_string())),
+        r#ref: Some(Some(1)),
+    };
+    assert_eq!(
+        RequiredItem::try_from(partial),
+        Ok(RequiredItem {
+            r#type: "a".to_string(),
+            r#ref: 1,
+        })
+    );
+}